@@ -0,0 +1,262 @@
+//! A blocking interface for tools and scripts that do not run inside a tokio runtime
+//!
+//! ``` rust,ignore
+//! use grandma2::{blocking::GrandMa2, FaderExecutor, MaEvent};
+//!
+//! let mut grandma = GrandMa2::connect("ws://localhost", "remote", "remote")?;
+//! grandma.subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))?;
+//! grandma.set_fader(FaderExecutor::new(1, 1), 1.0)?;
+//! for event in grandma.events()? {
+//!     println!("{event:?}");
+//! }
+//! ```
+
+use std::{
+    sync::mpsc,
+    thread::{self, JoinHandle},
+};
+
+use tokio::runtime::Builder;
+
+use crate::{
+    interface::{EventFilter, EventStream, GrandMa2 as AsyncGrandMa2, MaEvent},
+    ButtonData, ButtonExecutor, ClientStats, FaderData, FaderExecutor, Ma2Error, Result,
+    SubscriptionId,
+};
+
+/// Connection to a desk driven by a background thread with its own runtime
+///
+/// Dropping it closes the connection and waits for the background thread to finish.
+#[derive(Debug)]
+pub struct GrandMa2 {
+    inner: AsyncGrandMa2,
+    thread: Option<JoinHandle<Result<()>>>,
+}
+
+impl GrandMa2 {
+    /// Connects to the desk and waits until the login has been accepted
+    pub fn connect(
+        url: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Result<Self> {
+        Self::connect_with(AsyncGrandMa2::new(url, username, password))
+    }
+
+    /// Like [`GrandMa2::connect`], for a client that has already been configured
+    pub fn connect_with(mut grandma: AsyncGrandMa2) -> Result<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(Ma2Error::FailedToStartRuntime)?;
+        let (tx_connected, rx_connected) = mpsc::channel();
+
+        let thread = thread::Builder::new()
+            .name("grandma2".to_owned())
+            .spawn(move || {
+                runtime.block_on(async move {
+                    let mut client = match grandma.connect().await {
+                        Ok(client) => client,
+                        Err(err) => {
+                            let _ = tx_connected.send(Err(err));
+                            return Ok(());
+                        }
+                    };
+
+                    let run = client.run();
+                    tokio::pin!(run);
+                    tokio::select! {
+                        result = &mut run => {
+                            // The client stopped before the login, e.g. because it was rejected
+                            let err = result.err().unwrap_or(Ma2Error::ConnectionLost.into());
+                            let _ = tx_connected.send(Err(err));
+                            return Ok(());
+                        }
+                        login = grandma.wait_for_login() => {
+                            // On failure `grandma` is dropped, which stops the client
+                            let _ = tx_connected.send(login.map(|_| grandma));
+                        }
+                    }
+                    run.await
+                })
+            })
+            .map_err(Ma2Error::FailedToStartRuntime)?;
+
+        let connected = rx_connected.recv().map_err(|_| Ma2Error::ConnectionLost);
+        match connected {
+            Ok(Ok(inner)) => Ok(Self {
+                inner,
+                thread: Some(thread),
+            }),
+            Ok(Err(err)) => {
+                let _ = thread.join();
+                Err(err)
+            }
+            Err(err) => {
+                let _ = thread.join();
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Watch the faders from `start` to `end`, see [`AsyncGrandMa2::subscribe_fader`]
+    pub fn subscribe_fader(
+        &mut self,
+        start: FaderExecutor,
+        end: FaderExecutor,
+    ) -> Result<SubscriptionId> {
+        self.inner.subscribe_fader(start, end)
+    }
+
+    /// Watch the buttons from `start` to `end`, see [`AsyncGrandMa2::subscribe_button`]
+    pub fn subscribe_button(
+        &mut self,
+        start: ButtonExecutor,
+        end: ButtonExecutor,
+    ) -> Result<SubscriptionId> {
+        self.inner.subscribe_button(start, end)
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> Result<()> {
+        self.inner.unsubscribe(id)
+    }
+
+    /// The last known state of a subscribed fader
+    pub fn get_fader(&self, fader: FaderExecutor) -> Option<FaderData> {
+        self.inner.get_fader(fader)
+    }
+
+    /// The last known state of a subscribed button
+    pub fn get_button(&self, button: ButtonExecutor) -> Option<ButtonData> {
+        self.inner.get_button(button)
+    }
+
+    /// Message counts, poll latency, reconnects and other connection health
+    pub fn stats(&self) -> ClientStats {
+        self.inner.stats()
+    }
+
+    /// Move a fader to `value` which ranges from `0.0` to `1.0`
+    pub fn set_fader(&mut self, fader: FaderExecutor, value: f32) -> Result<()> {
+        self.inner.set_fader(fader, value)
+    }
+
+    pub fn press_button(&mut self, button: ButtonExecutor) -> Result<()> {
+        self.inner.press_button(button)
+    }
+
+    pub fn release_button(&mut self, button: ButtonExecutor) -> Result<()> {
+        self.inner.release_button(button)
+    }
+
+    pub fn command(&mut self, command: impl ToString) -> Result<()> {
+        self.inner.command(command)
+    }
+
+    /// All events from now on, the iterator ends once the connection has been closed
+    pub fn events(&self) -> Result<Events> {
+        self.events_filtered(EventFilter::new())
+    }
+
+    /// The events matching `filter` from now on
+    pub fn events_filtered(&self, filter: EventFilter) -> Result<Events> {
+        Ok(Events {
+            stream: self.inner.subscribe_filtered(filter)?,
+        })
+    }
+
+    /// Closes the connection and waits for the background thread to finish
+    ///
+    /// Returns the error that stopped the client, if any.
+    pub fn close(mut self) -> Result<()> {
+        self.shut_down()
+    }
+
+    fn shut_down(&mut self) -> Result<()> {
+        self.inner.close_connection();
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(_)) => Err(Ma2Error::ConnectionLost.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for GrandMa2 {
+    fn drop(&mut self) {
+        let _ = self.shut_down();
+    }
+}
+
+/// Blocking iterator over desk events, see [`GrandMa2::events`]
+///
+/// Can be moved to another thread. Missed events are reported as [`MaEvent::Lagged`].
+#[derive(Debug)]
+pub struct Events {
+    stream: EventStream,
+}
+
+impl Iterator for Events {
+    type Item = MaEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stream.blocking_recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::mock::MockServer;
+
+    fn eventually(condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition() {
+            assert!(Instant::now() < deadline, "timed out waiting for condition");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_blocking() {
+        let runtime = Builder::new_multi_thread().enable_all().build().unwrap();
+        let server = runtime.block_on(MockServer::start()).unwrap();
+
+        let mut grandma = GrandMa2::connect(server.url(), "remote", "remote").unwrap();
+        let events = grandma.events().unwrap();
+        grandma
+            .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+            .unwrap();
+        grandma.set_fader(FaderExecutor::new(1, 2), 0.75).unwrap();
+        grandma.command("Go Executor 1.3").unwrap();
+
+        let data = events
+            .filter_map(|event| match event {
+                MaEvent::FaderChanged(data) if (data.value() - 0.75).abs() < 0.001 => Some(data),
+                _ => None,
+            })
+            .next()
+            .unwrap();
+        assert_eq!(data.get_executer(), &FaderExecutor::new(1, 2));
+        eventually(|| {
+            server
+                .received()
+                .iter()
+                .any(|request| request["command"] == "Go Executor 1.3")
+        });
+
+        grandma.close().unwrap();
+        eventually(|| server.connections() == 0);
+    }
+
+    #[test]
+    fn test_login_failed() {
+        let runtime = Builder::new_multi_thread().enable_all().build().unwrap();
+        let server = runtime.block_on(MockServer::start()).unwrap();
+
+        let err = GrandMa2::connect(server.url(), "remote", "wrong").unwrap_err();
+        assert!(matches!(*err, Ma2Error::LoginFailed { .. }));
+    }
+}
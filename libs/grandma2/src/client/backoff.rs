@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+/// Exponential backoff used to reconnect to the desk after the connection has been lost
+///
+/// The n-th attempt waits `initial_delay * factor^n`, capped at `max_delay`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backoff {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub factor: u32,
+    /// Give up after this many attempts, `None` retries forever
+    pub max_attempts: Option<u32>,
+}
+
+impl Backoff {
+    pub fn new(initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            initial_delay,
+            max_delay,
+            ..Default::default()
+        }
+    }
+
+    /// Time to wait before the given (zero based) reconnect attempt
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.factor
+            .checked_pow(attempt)
+            .and_then(|factor| self.initial_delay.checked_mul(factor))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            factor: 2,
+            max_attempts: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_delay() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(backoff.delay(0), Duration::from_millis(100));
+        assert_eq!(backoff.delay(1), Duration::from_millis(200));
+        assert_eq!(backoff.delay(3), Duration::from_millis(800));
+        assert_eq!(backoff.delay(4), Duration::from_secs(1));
+        assert_eq!(backoff.delay(100), Duration::from_secs(1));
+    }
+}
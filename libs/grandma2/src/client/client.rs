@@ -1,4 +1,9 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
+    sync::{Arc, PoisonError, RwLock},
+    time::Duration,
+};
 
 extern crate md5;
 
@@ -8,23 +13,45 @@ use futures_util::{
 };
 use tokio::{
     net::TcpStream,
-    sync::mpsc::{UnboundedReceiver, UnboundedSender},
-    time::{interval, Interval},
+    sync::{broadcast, mpsc::UnboundedReceiver, oneshot, Notify},
+    time::{interval, sleep, sleep_until, timeout, Instant, Interval},
 };
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
 use tungstenite::Message;
 
-use crate::{client::ReceiveMsg, types::{ButtonRange, FaderRange}};
+use crate::client::ReceiveMsg;
 use crate::{
     interface::{MaEvent, MaRequest},
-    types::{ButtonData, FaderData, Ma2Data},
-    ButtonExecutor, FaderExecutor,
+    types::{ButtonData, ButtonState, CueStatus, DataKey, DeskData, FaderData, Ma2Data},
+    ButtonExecutor, Executor, FaderExecutor,
 };
 use crate::{Ma2Error, Result};
 
-use super::{messages::{Request, Response}, SendMsg};
+#[cfg(feature = "tls")]
+use super::TlsConfig;
+use super::{
+    backoff::Backoff,
+    faders::FaderThrottle,
+    messages::{Request, Response},
+    pending::{Pending, PendingRequests},
+    polling::Poller,
+    recorder::{Frame, RecordEvent, Recorder},
+    serde::{TYPE_BUTTON, TYPE_FADER},
+    stats::ClientStats,
+    subscriptions::{PlaybacksPoll, Subscription, Subscriptions},
+    SendMsg,
+};
 
-const MAX_REQUESTS: u8 = 9;
+/// Number of responses after which the session is renewed, the desk stops answering at 10
+const SESSION_RENEWAL: u8 = 9;
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const FADER_INTERVAL: Duration = Duration::from_millis(20);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_PENDING_POLLS: usize = 32;
+/// How long to wait for the desk to answer the close frame
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(1);
 
 #[derive(Debug)]
 pub struct GrandMa2Client {
@@ -32,17 +59,28 @@ pub struct GrandMa2Client {
     ws_sink: SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>,
     ws_stream: SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>,
     rx_request: UnboundedReceiver<MaRequest>,
-    tx_event: UnboundedSender<MaEvent>,
+    tx_event: broadcast::Sender<MaEvent>,
+    shutdown: Arc<Notify>,
     interval: Interval,
+    recorder: Option<Recorder>,
 
     // config
-    username: String,
-    password: String,
-    subscribed_button_range: Option<ButtonRange>,
-    subscribed_fader_range: Option<FaderRange>,
+    config: ClientConfig,
+    subscriptions: Subscriptions,
+    data_poll: Vec<DataKey>,
 
     // state
-    state: Ma2State,
+    state: SharedState,
+    /// Pages of the polls sent, with the time they have been sent
+    pending_pages: VecDeque<(u8, Instant)>,
+    next_stats: Option<Instant>,
+    pending: PendingRequests,
+    poller: Poller,
+    faders: FaderThrottle,
+    /// Last reported cues of every executor
+    cues: HashMap<Executor, CueStatus>,
+    /// Last page the desk reported for every requested page
+    desk_pages: HashMap<u8, u8>,
     logged_in: bool,
     session_id: i8,
     num_requests: u8,
@@ -50,82 +88,234 @@ pub struct GrandMa2Client {
 
 impl GrandMa2Client {
     /// Creates a new GrandMa2 Client
-    pub fn new(
+    pub(crate) fn new(
         ws_sink: WebSocketStream<MaybeTlsStream<TcpStream>>,
         rx_request: UnboundedReceiver<MaRequest>,
-        tx_event: UnboundedSender<MaEvent>,
-        username: impl Into<String>,
-        password: impl Into<String>,
+        tx_event: broadcast::Sender<MaEvent>,
+        shutdown: Arc<Notify>,
+        config: ClientConfig,
+        state: SharedState,
+        recorder: Option<Recorder>,
     ) -> Self {
         let (ws_sink, ws_stream) = ws_sink.split();
+        let pending = PendingRequests::new(config.request_timeout);
+        let poller = Poller::new(config.poll_interval, config.idle_poll_interval);
+        let faders = FaderThrottle::new(config.fader_interval);
         Self {
             // internals
             ws_sink,
             ws_stream,
             rx_request,
             tx_event,
-            interval: interval(Duration::from_millis(100)),
+            shutdown,
+            interval: interval(config.poll_interval),
+            recorder,
 
             // config
-            username: username.into(),
-            password: password.into(),
-            subscribed_button_range: None,
-            subscribed_fader_range: None,
+            config,
+            subscriptions: Subscriptions::new(),
+            data_poll: Vec::new(),
 
             // state
             logged_in: false,
             session_id: -1,
             num_requests: 0,
-            state: Ma2State::new(),
+            state,
+            pending_pages: VecDeque::new(),
+            next_stats: None,
+            pending,
+            poller,
+            faders,
+            cues: HashMap::new(),
+            desk_pages: HashMap::new(),
         }
     }
 
     /// This method is running the main loop for the GrandMa2 client
     ///
     /// `run` has be executed right after the connection has been established.
+    /// If the websocket connection is lost, the client reconnects according to its [`Backoff`]
+    /// and only returns once reconnecting has been given up or [`crate::GrandMa2`] has been
+    /// closed.
     pub async fn run(&mut self) -> Result<()> {
         loop {
-            tokio::select! {
+            let result = tokio::select! {
+                _ = self.shutdown.notified() => {
+                    self.shut_down().await;
+                    return Ok(());
+                }
                 msg = self.rx_request.recv() => {
                     match msg {
-                        Some(msg) => {
-                            let close = self.handle_request(msg).await?;
-
+                        Some(msg) => match self.handle_request(msg).await {
                             // Execute if a close message has been handled
-                            if close {
-                                return Ok(())
-                            }
+                            Ok(true) => return Ok(()),
+                            result => result.map(|_| ()),
+                        }
+                        // GrandMa2 has been dropped
+                        None => {
+                            self.close_connection().await;
+                            return Ok(());
                         }
-                        None => {return Err(Ma2Error::RequestChannelClosed.into());}
                     }
                 }
-                Some(Ok(msg)) = self.ws_stream.next() => {
-                    // println!("[GrandMa2] Receiving RAW {msg:?}");
+                msg = self.ws_stream.next() => {
                     match msg {
-                        Message::Text(msg_string) => {
-                            let msg: ReceiveMsg = serde_json::from_value(serde_json::from_str(&msg_string)
-                                .unwrap()).map_err(|err| Ma2Error::CouldNotDeserializeReceiveMsg(msg_string, Box::new(err)))?;
-                            println!("[GrandMa2] Receive: {:?}", msg);
-                            self.handle_ma2_message(msg).await?;
-                        }
-                        Message::Ping(data) => {
-                            // Does this still work without
-                            //self.send_ma2_raw(Message::Pong(data)).await?;
-                        }
-                        _ => {todo!("Cannot handle this")}
+                        Some(Ok(msg)) => self.handle_ws_message(msg).await,
+                        Some(Err(_)) | None => Err(Ma2Error::ConnectionLost.into()),
                     }
                 },
-                _ = self.interval.tick() => {
-                    self.on_interval().await?;
+                now = self.interval.tick() => {
+                    self.on_interval(now).await
+                }
+                _ = sleep_until(self.faders.next_due().unwrap_or_else(Instant::now)),
+                    if self.faders.next_due().is_some() =>
+                {
+                    self.send_faders(Instant::now()).await
+                }
+            };
+
+            match result {
+                Ok(()) => {}
+                Err(err) if err.is_connection_lost() => {
+                    if !self.reconnect().await? {
+                        return Ok(());
+                    }
+                }
+                Err(err) if err.is_fatal() => return Err(err),
+                // Anything else only affects a single message or request
+                Err(err) if err.is_warning() => {
+                    self.send_interface(MaEvent::Warning(Arc::new(*err)))?
+                }
+                Err(err) => self.send_interface(MaEvent::Error(Arc::new(*err)))?,
+            }
+        }
+    }
+
+    async fn handle_ws_message(&mut self, msg: Message) -> Result<()> {
+        // println!("[GrandMa2] Receiving RAW {msg:?}");
+        self.record(RecordEvent::Received, &msg);
+        self.update_stats(|stats| stats.messages_received += 1);
+        match msg {
+            Message::Text(msg_string) => {
+                let msg: ReceiveMsg = serde_json::from_str(&msg_string)
+                    .and_then(serde_json::from_value)
+                    .map_err(|err| {
+                        self.update_stats(|stats| stats.deserialize_failures += 1);
+                        Ma2Error::CouldNotDeserializeReceiveMsg(msg_string, Box::new(err))
+                    })?;
+                println!("[GrandMa2] Receive: {:?}", msg);
+                self.handle_ma2_message(msg).await?;
+            }
+            // Tungstenite queues the pong, flushing sends it right away
+            Message::Ping(_) => self
+                .ws_sink
+                .flush()
+                .await
+                .map_err(Ma2Error::WebsocketFailedToSend)?,
+            Message::Pong(_) => {}
+            Message::Close(frame) => {
+                // Complete the close handshake started by the desk, then reconnect
+                println!("[GrandMa2] Desk closed the connection: {frame:?}");
+                self.record(RecordEvent::Sent, &Message::Close(None));
+                let _ = self.ws_sink.flush().await;
+                return Err(Ma2Error::ConnectionLost.into());
+            }
+            Message::Binary(data) => {
+                let frame = format!("binary ({} bytes)", data.len());
+                return Err(Ma2Error::UnexpectedFrame(frame).into());
+            }
+            Message::Frame(frame) => {
+                return Err(Ma2Error::UnexpectedFrame(frame.to_string()).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Reopens the websocket after the connection has been lost
+    ///
+    /// Subscriptions are kept, the session and login handshake is started again by the desk
+    /// once the new connection is ready. All executors are reported again afterwards.
+    /// Returns `false` if the client has been shut down while reconnecting.
+    async fn reconnect(&mut self) -> Result<bool> {
+        let backoff = match &self.config.backoff {
+            Some(backoff) => backoff.clone(),
+            None => return Err(Ma2Error::ConnectionLost.into()),
+        };
+
+        println!("[GrandMa2] Connection lost");
+        self.reset_session();
+        self.send_interface(MaEvent::Disconnected)?;
+
+        let mut attempt = 0;
+        loop {
+            if backoff.max_attempts.is_some_and(|max_attempts| attempt >= max_attempts) {
+                return Err(Ma2Error::ReconnectFailed {
+                    url: self.config.url.clone(),
+                    attempts: attempt,
                 }
+                .into());
+            }
+            let delay = backoff.delay(attempt);
+            attempt += 1;
+
+            let connected = tokio::select! {
+                _ = self.shutdown.notified() => return Ok(false),
+                connected = async {
+                    sleep(delay).await;
+                    connect_websocket(&self.config).await
+                } => connected,
+            };
+            match connected {
+                Ok(ws_stream) => {
+                    (self.ws_sink, self.ws_stream) = ws_stream.split();
+                    if let Some(recorder) = self.recorder.as_mut() {
+                        let url = self.config.url.clone();
+                        let _ = recorder.record(RecordEvent::Connected(url));
+                    }
+                    println!("[GrandMa2] Reconnected after {attempt} attempt(s)");
+                    self.update_stats(|stats| stats.reconnects += 1);
+                    self.send_interface(MaEvent::Reconnected)?;
+                    return Ok(true);
+                }
+                Err(err) => println!("[GrandMa2] Reconnect attempt {attempt} failed: {err}"),
+            }
+        }
+    }
+
+    /// Sends all requests queued before the shutdown, then closes the connection
+    async fn shut_down(&mut self) {
+        while let Ok(msg) = self.rx_request.try_recv() {
+            match self.handle_request(msg).await {
+                // The connection has already been closed
+                Ok(true) => return,
+                Ok(false) => {}
+                Err(err) => println!("[GrandMa2] Request failed during shutdown: {err}"),
             }
         }
+        self.close_connection().await;
+    }
+
+    fn reset_session(&mut self) {
+        self.logged_in = false;
+        self.session_id = -1;
+        self.num_requests = 0;
+        self.pending_pages.clear();
+        self.pending.fail_all(|| Ma2Error::ConnectionLost);
+        self.poller.reset(Instant::now());
+        self.faders.reset();
+        self.cues.clear();
+        self.desk_pages.clear();
+        self.state
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
     }
 
     async fn handle_ma2_message(&mut self, msg: ReceiveMsg) -> Result<()> {
         // Handle weird GrandMa behaviour
         self.num_requests += 1;
-        if self.num_requests >= MAX_REQUESTS {
+        if self.num_requests >= self.config.session_renewal {
+            self.update_stats(|stats| stats.session_renewals += 1);
             self.send_session().await?;
             self.num_requests = 0;
         }
@@ -162,18 +352,59 @@ impl GrandMa2Client {
 
                 if result {
                     self.logged_in = true;
+                    self.pending.resolve_login(|| Ok(()));
                     Ok(())
                 } else {
-                    Err(Ma2Error::LoginFailed {
-                        username: self.username.clone(),
-                        password: self.password.clone(),
-                        hashed_password: self.get_hashed_password(),
-                    }
-                    .into())
+                    let config = &self.config;
+                    self.pending.resolve_login(|| Err(login_error(config).into()));
+                    Err(login_error(config).into())
                 }
             }
-            ReceiveMsg::Response(Response::Playbacks { item_groups, .. }) => {
-                let diff = self.state.diff_and_update(item_groups);
+            ReceiveMsg::Response(Response::Playbacks {
+                i_page,
+                item_groups,
+                ..
+            }) => {
+                // The desk answers with a different page if it has been told to show another one
+                let requested_page = self.pending_pages.pop_front().map(|(page, sent)| {
+                    self.update_stats(|stats| stats.record_poll_latency(sent.elapsed()));
+                    page
+                });
+                let changed = requested_page.is_some_and(|page| {
+                    self.desk_pages.insert(page, i_page).unwrap_or(page) != i_page
+                });
+                if changed {
+                    self.send_interface(MaEvent::PageChanged(i_page))?;
+                }
+
+                self.pending.resolve_playbacks(&item_groups);
+                let diff = self
+                    .state
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .diff_and_update(item_groups);
+                let changed: Vec<Executor> = diff
+                    .fader_data
+                    .iter()
+                    .map(|data| data.get_executer().executor())
+                    .chain(diff.button_data.iter().map(|data| data.get_executer().executor()))
+                    .collect();
+                if !changed.is_empty() {
+                    self.poller.changed(&changed, Instant::now());
+                }
+                for data in diff.fader_data.iter() {
+                    self.faders.reported(*data.get_executer(), data.value());
+                }
+                let cues: Vec<CueStatus> = diff
+                    .fader_data
+                    .iter()
+                    .map(|data| CueStatus::new(*data.get_executer(), data.info()))
+                    .chain(
+                        diff.button_data
+                            .iter()
+                            .map(|data| CueStatus::new(*data.get_executer(), data.info())),
+                    )
+                    .collect();
                 for channel in diff.fader_data {
                     let msg = MaEvent::FaderChanged(channel);
                     self.send_interface(msg)?;
@@ -182,12 +413,42 @@ impl GrandMa2Client {
                     let msg = MaEvent::ButtonChanged(channel);
                     self.send_interface(msg)?;
                 }
+                for status in cues {
+                    if self.update_cue(&status) {
+                        self.send_interface(MaEvent::CueChanged(status))?;
+                    }
+                }
+                Ok(())
+            }
+            ReceiveMsg::Text { text } => Err(Ma2Error::DeskMessage(text.into()).into()),
+            ReceiveMsg::Response(Response::GetData { data, .. }) => {
+                let data = data.desk_data();
+                self.pending.resolve_data(&data);
+
+                let changes = self
+                    .state
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .update_data(&data);
+                if !changes.is_empty() {
+                    self.send_interface(MaEvent::DataChanged(changes))?;
+                }
                 Ok(())
             }
             _ => Err(Ma2Error::MessageHandlerNotImplemented(msg).into()),
         }
     }
 
+    /// Remembers the cues of an executor, returns whether they changed
+    ///
+    /// Executors without cues are only reported once they had cues before.
+    fn update_cue(&mut self, status: &CueStatus) -> bool {
+        match self.cues.insert(status.executor, status.clone()) {
+            Some(previous) => &previous != status,
+            None => !status.is_empty(),
+        }
+    }
+
     async fn handle_request(&mut self, msg: MaRequest) -> Result<bool> {
         match msg {
             // Disconnect
@@ -195,51 +456,190 @@ impl GrandMa2Client {
                 self.close_connection().await;
                 return Ok(true);
             }
-            MaRequest::SubscribeButton(start, end) => {
-                self.subscribed_button_range = Some(ButtonRange::from(start, end)?);
+            MaRequest::SubscribeButton(id, range) => {
+                self.subscriptions.insert(id, Subscription::Button(range));
+                self.update_polls();
+            }
+            MaRequest::SubscribeFader(id, range) => {
+                self.subscriptions.insert(id, Subscription::Fader(range));
+                self.update_polls();
+            }
+            MaRequest::Unsubscribe(id) => {
+                self.subscriptions.remove(id);
+                self.update_polls();
+            }
+            MaRequest::SetPollPriority(id, priority) => {
+                self.subscriptions.set_priority(id, priority);
+                self.update_polls();
+            }
+            MaRequest::PollStats(reply) => {
+                let _ = reply.send(self.poller.stats());
+            }
+            MaRequest::SetButton(button, state) => {
+                self.send_button_input(button.executor(), 0, state).await?;
+            }
+            MaRequest::SetFader(fader, value) => {
+                self.faders.set(fader, value);
+                self.send_faders(Instant::now()).await?;
+            }
+            MaRequest::SetFaderButton(fader, button, state) => {
+                self.send_button_input(fader.executor(), button.id(), state)
+                    .await?;
+            }
+            MaRequest::Command(command) => {
+                let msg = SendMsg::Request(Request::Command {
+                    command,
+                    max_requests: 0,
+                    session: self.session_id,
+                });
+                self.send_ma2_msg(msg).await?;
             }
-            MaRequest::SubscribeFader(start, end) => {
-                self.subscribed_fader_range = Some(FaderRange::from(start, end)?);
+            MaRequest::SetFaderConfirmed(fader, value, reply) => {
+                self.faders.sent(fader, Instant::now());
+                self.send_fader_input(fader.executor(), value).await?;
+                self.pending.push(Pending::Fader {
+                    fader,
+                    value: value.clamp(0.0, 1.0),
+                    reply,
+                });
+                self.send_playbacks(PlaybacksPoll::single(fader.executor(), TYPE_FADER))
+                    .await?;
             }
-            _ => {
-                todo!("Cannot handle message {:?}", msg)
+            MaRequest::SetButtonConfirmed(button, state, reply) => {
+                self.send_button_input(button.executor(), 0, state).await?;
+                self.pending.push(Pending::Button {
+                    button,
+                    state,
+                    reply,
+                });
+                self.send_playbacks(PlaybacksPoll::single(button.executor(), TYPE_BUTTON))
+                    .await?;
+            }
+            MaRequest::WaitForLogin(reply) => {
+                if self.logged_in {
+                    let _ = reply.send(Ok(()));
+                } else {
+                    self.pending.push(Pending::Login(reply));
+                }
+            }
+            MaRequest::GetData(keys, tx_data) => {
+                self.send_get_data(&keys, Some(tx_data)).await?;
+            }
+            MaRequest::PollData(keys) => {
+                self.data_poll = keys;
             }
         };
         Ok(false)
     }
 
-    async fn on_interval(&mut self) -> Result<()> {
-        if let Some(subscribed_button_range) = self.subscribed_button_range {
-            let msg = SendMsg::Request(Request::Playbacks {
-                start_index: vec![subscribed_button_range.start_button.id().into()],
-                items_count: vec![subscribed_button_range.num_buttons.into()],
-                page_index: 0,
-                items_type: vec![3],
-                view: 2,
-                exec_button_view_mode: 1,
-                buttons_view_mode: 0,
-                max_requests: 1,
-                session: self.session_id,
-            });
-            self.send_ma2_msg(msg).await?
+    async fn send_get_data(
+        &mut self,
+        keys: &[DataKey],
+        tx_data: Option<oneshot::Sender<Result<DeskData>>>,
+    ) -> Result<()> {
+        let data = keys
+            .iter()
+            .map(DataKey::as_str)
+            .collect::<Vec<_>>()
+            .join(",");
+        let msg = SendMsg::Request(Request::GetData {
+            data,
+            max_requests: self.config.max_requests,
+            session: self.session_id,
+        });
+        self.send_ma2_msg(msg).await?;
+        self.pending.push(Pending::Data(tx_data));
+        Ok(())
+    }
+
+    async fn send_button_input(
+        &mut self,
+        executor: Executor,
+        button_id: u16,
+        state: ButtonState,
+    ) -> Result<()> {
+        self.poller.activity(executor, Instant::now());
+        let msg = SendMsg::Request(Request::ButtonInput {
+            exec_index: executor.exec_index(),
+            page_index: executor.page_index(),
+            button_id,
+            input_type: 0,
+            pressed: state == ButtonState::Pressed,
+            released: state == ButtonState::Released,
+            max_requests: 0,
+            session: self.session_id,
+        });
+        self.send_ma2_msg(msg).await
+    }
+
+    /// Sends the fader values that are due, see [`FaderThrottle`]
+    async fn send_faders(&mut self, now: Instant) -> Result<()> {
+        for (fader, value) in self.faders.due(now) {
+            self.send_fader_input(fader.executor(), value).await?;
+        }
+        Ok(())
+    }
+
+    async fn send_fader_input(&mut self, executor: Executor, value: f32) -> Result<()> {
+        self.poller.activity(executor, Instant::now());
+        let msg = SendMsg::Request(Request::FaderInput {
+            exec_index: executor.exec_index(),
+            page_index: executor.page_index(),
+            fader_value: value.clamp(0.0, 1.0),
+            input_type: 1,
+            max_requests: 0,
+            session: self.session_id,
+        });
+        self.send_ma2_msg(msg).await
+    }
+
+    fn update_polls(&mut self) {
+        self.poller.set_polls(self.subscriptions.polls(), Instant::now());
+    }
+
+    async fn on_interval(&mut self, now: Instant) -> Result<()> {
+        self.pending.expire(Instant::now());
+        self.send_stats(now)?;
+
+        // The desk ignores polls until the login succeeded
+        if !self.logged_in {
+            return Ok(());
+        }
+
+        for poll in self.poller.due(now) {
+            self.send_playbacks(poll).await?;
         }
-        if let Some(subscribed_fader_range) = self.subscribed_fader_range {
-            let msg = SendMsg::Request(Request::Playbacks {
-                start_index: vec![subscribed_fader_range.start_fader.id().into()],
-                items_count: vec![subscribed_fader_range.num_faders.into()],
-                page_index: 0,
-                items_type: vec![2],
-                view: 2,
-                exec_button_view_mode: 1,
-                buttons_view_mode: 0,
-                max_requests: 1,
-                session: self.session_id,
-            });
-            self.send_ma2_msg(msg).await?
+
+        // Only poll the console state again once the last poll has been answered
+        if !self.data_poll.is_empty() && !self.pending.data_poll_pending() {
+            let keys = self.data_poll.clone();
+            self.send_get_data(&keys, None).await?;
         }
         Ok(())
     }
 
+    async fn send_playbacks(&mut self, poll: PlaybacksPoll) -> Result<()> {
+        let msg = SendMsg::Request(Request::Playbacks {
+            start_index: poll.start_index,
+            items_count: poll.items_count,
+            page_index: poll.page.saturating_sub(1),
+            items_type: poll.items_type,
+            view: 2,
+            exec_button_view_mode: 1,
+            buttons_view_mode: 0,
+            max_requests: self.config.max_requests,
+            session: self.session_id,
+        });
+        self.send_ma2_msg(msg).await?;
+
+        // Forget about polls the desk never answered
+        if self.pending_pages.len() >= MAX_PENDING_POLLS {
+            self.pending_pages.pop_front();
+        }
+        self.pending_pages.push_back((poll.page, Instant::now()));
+        Ok(())
+    }
+
     async fn send_ma2_msg(&mut self, msg: SendMsg) -> Result<()> {
         println!("[GrandMa2] Sending: {:?}", msg);
         let msg_string =
@@ -249,15 +649,57 @@ impl GrandMa2Client {
     }
 
     async fn send_ma2_raw(&mut self, msg: Message) -> Result<()> {
+        self.record(RecordEvent::Sent, &msg);
+        self.update_stats(|stats| stats.messages_sent += 1);
         self.ws_sink
             .send(msg)
             .await
             .map_err(|e| Ma2Error::WebsocketFailedToSend(e).into())
     }
 
+    /// Writes the frame to the recording, recording stops after the first failure
+    fn record(&mut self, event: fn(Frame) -> RecordEvent, msg: &Message) {
+        let (Some(recorder), Some(frame)) = (self.recorder.as_mut(), Frame::from_message(msg))
+        else {
+            return;
+        };
+        if let Err(err) = recorder.record(event(frame)) {
+            self.recorder = None;
+            let _ = self.send_interface(MaEvent::Error(Arc::new(*err)));
+        }
+    }
+
+    fn update_stats(&self, f: impl FnOnce(&mut ClientStats)) {
+        f(&mut self
+            .state
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .stats);
+    }
+
+    /// Reports the stats every `stats_interval` if configured
+    fn send_stats(&mut self, now: Instant) -> Result<()> {
+        let Some(stats_interval) = self.config.stats_interval else {
+            return Ok(());
+        };
+        if self.next_stats.is_some_and(|next_stats| now < next_stats) {
+            return Ok(());
+        }
+        self.next_stats = Some(now + stats_interval);
+        let stats = self
+            .state
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .stats
+            .clone();
+        self.send_interface(MaEvent::Stats(stats))
+    }
+
+    /// Sends the event to all event streams, fails once all of them have been dropped
     fn send_interface(&self, message: MaEvent) -> Result<()> {
         self.tx_event
             .send(message)
+            .map(|_| ())
             .map_err(|_| Ma2Error::EventChannelClosed.into())
     }
 
@@ -269,8 +711,9 @@ impl GrandMa2Client {
     }
 
     async fn send_login(&mut self) -> Result<()> {
+        self.update_stats(|stats| stats.login_attempts += 1);
         let login_msg = SendMsg::Request(Request::Login {
-            username: self.username.clone(),
+            username: self.config.username.clone(),
             password: self.get_hashed_password(),
             max_requests: 10,
             session: self.session_id,
@@ -278,6 +721,7 @@ impl GrandMa2Client {
         self.send_ma2_msg(login_msg).await
     }
 
+    /// Logs out and closes the websocket with a close handshake
     async fn close_connection(&mut self) {
         let close_msg = SendMsg::Request(Request::Close {
             session: self.session_id,
@@ -287,18 +731,136 @@ impl GrandMa2Client {
         // Try to send logout and close the websocket
         // if it fails just accept it
         self.send_ma2_msg(close_msg).await.unwrap_or(());
+        self.record(RecordEvent::Sent, &Message::Close(None));
         self.ws_sink.close().await.unwrap_or(());
+
+        // Wait for the desk to acknowledge the close frame
+        let acknowledged = timeout(CLOSE_TIMEOUT, async {
+            while let Some(Ok(msg)) = self.ws_stream.next().await {
+                self.record(RecordEvent::Received, &msg);
+                if let Message::Close(_) = msg {
+                    return;
+                }
+            }
+        })
+        .await;
+        if acknowledged.is_err() {
+            println!("[GrandMa2] Desk did not acknowledge closing the connection");
+        }
+        self.pending.fail_all(|| Ma2Error::ConnectionLost);
+
+        if let Some(recorder) = self.recorder.as_mut() {
+            if let Err(err) = recorder.flush() {
+                let _ = self.send_interface(MaEvent::Error(Arc::new(*err)));
+            }
+        }
     }
 
     fn get_hashed_password(&self) -> String {
-        format!("{:x}", md5::compute(&self.password))
+        hash_password(&self.config.password)
+    }
+}
+
+fn hash_password(password: &str) -> String {
+    format!("{:x}", md5::compute(password))
+}
+
+fn login_error(config: &ClientConfig) -> Ma2Error {
+    Ma2Error::LoginFailed {
+        username: config.username.clone(),
+        password: config.password.clone(),
+        hashed_password: hash_password(&config.password),
     }
 }
 
+/// Connection settings of the client
+#[derive(Debug, Clone)]
+pub(crate) struct ClientConfig {
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    pub backoff: Option<Backoff>,
+    /// How long to wait for the desk to answer a request
+    pub request_timeout: Duration,
+    /// How often subscribed executors and data keys are polled while the desk is busy
+    pub poll_interval: Duration,
+    /// Longest interval executors are polled with while nothing changes
+    pub idle_poll_interval: Duration,
+    /// Number of responses after which the session is renewed
+    pub session_renewal: u8,
+    /// `maxRequests` sent with every poll
+    pub max_requests: u16,
+    /// Shortest interval between two inputs of the same fader
+    pub fader_interval: Duration,
+    /// How often [`MaEvent::Stats`] is sent, `None` never sends it
+    pub stats_interval: Option<Duration>,
+    /// How long opening the websocket may take
+    pub connect_timeout: Duration,
+    #[cfg(feature = "tls")]
+    pub tls: Option<TlsConfig>,
+    /// File the websocket traffic is recorded to
+    pub record: Option<PathBuf>,
+}
+
+impl ClientConfig {
+    pub fn new(url: String, username: String, password: String) -> Self {
+        Self {
+            url,
+            username,
+            password,
+            backoff: Some(Backoff::default()),
+            request_timeout: REQUEST_TIMEOUT,
+            poll_interval: POLL_INTERVAL,
+            idle_poll_interval: IDLE_POLL_INTERVAL,
+            session_renewal: SESSION_RENEWAL,
+            max_requests: 1,
+            fader_interval: FADER_INTERVAL,
+            stats_interval: None,
+            connect_timeout: CONNECT_TIMEOUT,
+            #[cfg(feature = "tls")]
+            tls: None,
+            record: None,
+        }
+    }
+}
+
+/// Opens the websocket connection to the web remote of the desk
+pub(crate) async fn connect_websocket(
+    config: &ClientConfig,
+) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+    #[cfg(feature = "tls")]
+    let connect = tokio_tungstenite::connect_async_tls_with_config(
+        config.url.as_str(),
+        None,
+        false,
+        config.tls.as_ref().map(TlsConfig::connector),
+    );
+    #[cfg(not(feature = "tls"))]
+    let connect = tokio_tungstenite::connect_async(config.url.as_str());
+
+    let (ws_stream, _response) = timeout(config.connect_timeout, connect)
+        .await
+        .map_err(|_| Ma2Error::Timeout {
+            request: format!("connect to {}", config.url),
+            timeout: config.connect_timeout,
+        })?
+        .map_err(|tungstenite_error| Ma2Error::FailedToConnect {
+            url: config.url.clone(),
+            tungstenite_error,
+        })?;
+    Ok(ws_stream)
+}
+
+/// The state of the desk, written by the client and read through [`crate::GrandMa2`]
+pub(crate) type SharedState = Arc<RwLock<Ma2State>>;
+
 #[derive(Debug)]
 pub struct Ma2State {
     buttons: HashMap<ButtonExecutor, ButtonData>,
     faders: HashMap<FaderExecutor, FaderData>,
+    data: DeskData,
+    /// Not cleared when the connection is lost
+    stats: ClientStats,
 }
 
 impl Ma2State {
@@ -306,9 +868,15 @@ impl Ma2State {
         Self {
             buttons: HashMap::new(),
             faders: HashMap::new(),
+            data: DeskData::new(),
+            stats: ClientStats::default(),
         }
     }
 
+    pub fn stats(&self) -> &ClientStats {
+        &self.stats
+    }
+
     pub fn get_button(&self, button: ButtonExecutor) -> Option<&ButtonData> {
         self.buttons.get(&button)
     }
@@ -317,6 +885,38 @@ impl Ma2State {
         self.faders.get(&fader)
     }
 
+    pub fn get_cues(&self, executor: Executor) -> Option<CueStatus> {
+        if let Some(fader) = executor.into_fader() {
+            return Some(CueStatus::new(executor, self.get_fader(fader)?.info()));
+        }
+        let button = executor.into_button()?;
+        Some(CueStatus::new(executor, self.get_button(button)?.info()))
+    }
+
+    /// All known faders and buttons, sorted by executor
+    pub fn snapshot(&self) -> Ma2Data {
+        let mut fader_data: Vec<FaderData> = self.faders.values().cloned().collect();
+        fader_data.sort_by_key(|fader| *fader.get_executer());
+        let mut button_data: Vec<ButtonData> = self.buttons.values().cloned().collect();
+        button_data.sort_by_key(|button| *button.get_executer());
+        Ma2Data::new(fader_data, button_data)
+    }
+
+    pub fn get_data(&self) -> &DeskData {
+        &self.data
+    }
+
+    pub fn clear(&mut self) {
+        self.faders.clear();
+        self.buttons.clear();
+        self.data.clear();
+    }
+
+    /// Takes over the values of a `getdata` response, returns the values that changed
+    pub fn update_data(&mut self, data: &DeskData) -> DeskData {
+        self.data.diff_and_update(data)
+    }
+
     pub fn diff_and_update(&mut self, data: Ma2Data) -> Ma2Data {
         let Ma2Data {
             fader_data,
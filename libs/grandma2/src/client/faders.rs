@@ -0,0 +1,141 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::FaderExecutor;
+
+/// Values closer than this are the same fader position, the desk reports three decimals
+const ECHO_TOLERANCE: f32 = 0.001;
+
+#[derive(Debug, Default)]
+struct FaderInput {
+    /// Latest value that still has to be sent
+    pending: Option<f32>,
+    last_sent: Option<Instant>,
+    /// Latest value reported by the desk since the last value has been sent
+    reported: Option<f32>,
+}
+
+/// Coalesces fader inputs and limits how often each fader is sent
+///
+/// Only the latest value of a fader is kept until it may be sent again. Values the desk just
+/// reported are not sent back, so motor faders following the desk do not cause a feedback
+/// loop.
+#[derive(Debug)]
+pub(crate) struct FaderThrottle {
+    interval: Duration,
+    faders: BTreeMap<FaderExecutor, FaderInput>,
+}
+
+impl FaderThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            faders: BTreeMap::new(),
+        }
+    }
+
+    /// Queues `value`, replacing a value that has not been sent yet
+    pub fn set(&mut self, fader: FaderExecutor, value: f32) {
+        self.faders.entry(fader).or_default().pending = Some(value.clamp(0.0, 1.0));
+    }
+
+    /// Records a value that has been sent without throttling
+    pub fn sent(&mut self, fader: FaderExecutor, now: Instant) {
+        let input = self.faders.entry(fader).or_default();
+        input.pending = None;
+        input.last_sent = Some(now);
+        input.reported = None;
+    }
+
+    /// Records a value reported by the desk
+    pub fn reported(&mut self, fader: FaderExecutor, value: f32) {
+        self.faders.entry(fader).or_default().reported = Some(value);
+    }
+
+    /// Values that have to be sent at `now`, echoes of reported values are dropped
+    pub fn due(&mut self, now: Instant) -> Vec<(FaderExecutor, f32)> {
+        let mut due = Vec::new();
+        for (fader, input) in self.faders.iter_mut() {
+            let Some(value) = input.pending else {
+                continue;
+            };
+            if input
+                .reported
+                .is_some_and(|reported| (reported - value).abs() < ECHO_TOLERANCE)
+            {
+                input.pending = None;
+                continue;
+            }
+            if input
+                .last_sent
+                .is_some_and(|last_sent| now < last_sent + self.interval)
+            {
+                continue;
+            }
+            input.pending = None;
+            input.last_sent = Some(now);
+            input.reported = None;
+            due.push((*fader, value));
+        }
+        due
+    }
+
+    /// When the next queued value may be sent, `None` if nothing is queued
+    pub fn next_due(&self) -> Option<Instant> {
+        self.faders
+            .values()
+            .filter(|input| input.pending.is_some())
+            .map(|input| match input.last_sent {
+                Some(last_sent) => last_sent + self.interval,
+                None => Instant::now(),
+            })
+            .min()
+    }
+
+    /// Forgets all values, e.g. after the connection has been lost
+    pub fn reset(&mut self) {
+        self.faders.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_throttle() {
+        let interval = Duration::from_millis(20);
+        let mut throttle = FaderThrottle::new(interval);
+        let fader = FaderExecutor::new(1, 1);
+        let start = Instant::now();
+
+        // The first value is sent right away, later ones are coalesced
+        throttle.set(fader, 0.1);
+        assert_eq!(throttle.due(start), [(fader, 0.1)]);
+        throttle.set(fader, 0.2);
+        throttle.set(fader, 0.3);
+        assert!(throttle.due(start + interval / 2).is_empty());
+        assert_eq!(throttle.next_due(), Some(start + interval));
+        assert_eq!(throttle.due(start + interval), [(fader, 0.3)]);
+        assert_eq!(throttle.next_due(), None);
+
+        // Echoes of reported values are not sent back
+        throttle.reported(fader, 0.5);
+        throttle.set(fader, 0.5005);
+        assert!(throttle.due(start + interval * 3).is_empty());
+        assert_eq!(throttle.next_due(), None);
+        throttle.set(fader, 0.6);
+        assert_eq!(throttle.due(start + interval * 3), [(fader, 0.6)]);
+
+        // Sent values are not echoes, the desk may have moved the fader since
+        throttle.set(fader, 0.6);
+        assert_eq!(throttle.due(start + interval * 4), [(fader, 0.6)]);
+
+        // Faders are limited independently
+        let other = FaderExecutor::new(1, 2);
+        throttle.set(fader, 0.7);
+        throttle.set(other, 0.7);
+        assert_eq!(throttle.due(start + interval * 4), [(other, 0.7)]);
+    }
+}
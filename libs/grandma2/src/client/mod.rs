@@ -1,6 +1,24 @@
+mod backoff;
 mod client;
+mod faders;
 mod messages;
+mod pending;
+mod polling;
+mod recorder;
 mod serde;
+mod stats;
+mod subscriptions;
+#[cfg(feature = "tls")]
+mod tls;
 
+pub use backoff::Backoff;
 pub use client::GrandMa2Client;
+pub(crate) use client::{connect_websocket, ClientConfig, Ma2State, SharedState};
 pub use messages::{ReceiveMsg, SendMsg};
+pub use polling::PollStats;
+pub(crate) use recorder::Recorder;
+pub use recorder::{Frame, Record, RecordEvent, Recording};
+pub use stats::ClientStats;
+pub use subscriptions::{PollPriority, SubscriptionId};
+#[cfg(feature = "tls")]
+pub use tls::TlsConfig;
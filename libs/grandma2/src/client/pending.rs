@@ -0,0 +1,254 @@
+use std::time::Duration;
+
+use tokio::{sync::oneshot, time::Instant};
+
+use crate::{
+    types::{ButtonData, DeskData, FaderData, Ma2Data},
+    ButtonExecutor, ButtonState, FaderExecutor, Ma2Error, Result,
+};
+
+/// The desk sends fader values with three decimals
+const FADER_TOLERANCE: f32 = 0.0015;
+
+/// A request that waits for a response of the desk
+#[derive(Debug)]
+pub(crate) enum Pending {
+    Login(oneshot::Sender<Result<()>>),
+    /// A `getdata` request, `None` for polls nobody is waiting for
+    Data(Option<oneshot::Sender<Result<DeskData>>>),
+    /// Resolved once the desk reports the fader at `value`
+    Fader {
+        fader: FaderExecutor,
+        value: f32,
+        reply: oneshot::Sender<Result<FaderData>>,
+    },
+    /// Resolved once the desk reports the button in `state`
+    Button {
+        button: ButtonExecutor,
+        state: ButtonState,
+        reply: oneshot::Sender<Result<ButtonData>>,
+    },
+}
+
+impl Pending {
+    fn description(&self) -> String {
+        match self {
+            Pending::Login(_) => "login".to_owned(),
+            Pending::Data(_) => "getdata".to_owned(),
+            Pending::Fader { fader, value, .. } => format!("fader {} at {value}", fader.executor()),
+            Pending::Button { button, state, .. } => {
+                format!("button {} {state:?}", button.executor())
+            }
+        }
+    }
+
+    fn fail(self, err: Ma2Error) {
+        // The caller may have given up waiting, so sending is allowed to fail
+        let err = Box::new(err);
+        match self {
+            Pending::Login(reply) => drop(reply.send(Err(err))),
+            Pending::Data(Some(reply)) => drop(reply.send(Err(err))),
+            Pending::Data(None) => {}
+            Pending::Fader { reply, .. } => drop(reply.send(Err(err))),
+            Pending::Button { reply, .. } => drop(reply.send(Err(err))),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PendingRequest {
+    pending: Pending,
+    deadline: Instant,
+}
+
+/// Requests waiting for the desk, in the order they have been sent
+///
+/// The web remote does not echo any request id, so responses are matched by their type and
+/// content. Requests that are not answered within the timeout fail with [`Ma2Error::Timeout`].
+#[derive(Debug)]
+pub(crate) struct PendingRequests {
+    requests: Vec<PendingRequest>,
+    timeout: Duration,
+}
+
+impl PendingRequests {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            requests: Vec::new(),
+            timeout,
+        }
+    }
+
+    pub fn push(&mut self, pending: Pending) {
+        self.requests.push(PendingRequest {
+            pending,
+            deadline: Instant::now() + self.timeout,
+        });
+    }
+
+    /// Whether a `getdata` poll is still waiting for its response
+    pub fn data_poll_pending(&self) -> bool {
+        self.requests
+            .iter()
+            .any(|request| matches!(request.pending, Pending::Data(None)))
+    }
+
+    pub fn resolve_login(&mut self, result: impl Fn() -> Result<()>) {
+        for pending in self.take(|pending| matches!(pending, Pending::Login(_))) {
+            if let Pending::Login(reply) = pending {
+                let _ = reply.send(result());
+            }
+        }
+    }
+
+    /// Answers the oldest `getdata` request
+    pub fn resolve_data(&mut self, data: &DeskData) {
+        let index = self
+            .requests
+            .iter()
+            .position(|request| matches!(request.pending, Pending::Data(_)));
+        if let Some(index) = index {
+            if let Pending::Data(Some(reply)) = self.requests.remove(index).pending {
+                let _ = reply.send(Ok(data.clone()));
+            }
+        }
+    }
+
+    /// Answers all fader and button requests confirmed by a playbacks response
+    pub fn resolve_playbacks(&mut self, data: &Ma2Data) {
+        let confirmed = self.take(|pending| match pending {
+            Pending::Fader { fader, value, .. } => data.faders().iter().any(|data| {
+                data.get_executer() == fader && (data.value() - value).abs() < FADER_TOLERANCE
+            }),
+            Pending::Button { button, state, .. } => data
+                .buttons()
+                .iter()
+                .any(|data| data.get_executer() == button && data.state() == *state),
+            _ => false,
+        });
+
+        for pending in confirmed {
+            match pending {
+                Pending::Fader { fader, reply, .. } => {
+                    let data = data
+                        .faders()
+                        .iter()
+                        .find(|data| data.get_executer() == &fader);
+                    let _ = reply.send(Ok(data.cloned().expect("Fader has been confirmed")));
+                }
+                Pending::Button { button, reply, .. } => {
+                    let data = data
+                        .buttons()
+                        .iter()
+                        .find(|data| data.get_executer() == &button);
+                    let _ = reply.send(Ok(data.cloned().expect("Button has been confirmed")));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Fails all requests whose deadline has passed
+    pub fn expire(&mut self, now: Instant) {
+        let timeout = self.timeout;
+        let (expired, requests) = std::mem::take(&mut self.requests)
+            .into_iter()
+            .partition(|request| request.deadline <= now);
+        self.requests = requests;
+
+        for request in expired {
+            let description = request.pending.description();
+            request.pending.fail(Ma2Error::Timeout {
+                request: description,
+                timeout,
+            });
+        }
+    }
+
+    /// Fails all requests, e.g. because the connection has been lost
+    pub fn fail_all(&mut self, err: impl Fn() -> Ma2Error) {
+        for request in self.requests.drain(..) {
+            request.pending.fail(err());
+        }
+    }
+
+    fn take(&mut self, mut filter: impl FnMut(&Pending) -> bool) -> Vec<Pending> {
+        let (taken, requests) = std::mem::take(&mut self.requests)
+            .into_iter()
+            .partition(|request| filter(&request.pending));
+        self.requests = requests;
+        taken.into_iter().map(|request| request.pending).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::DataKey;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_resolve_and_expire() {
+        let mut pending = PendingRequests::new(Duration::from_secs(1));
+        let (tx_fader, mut rx_fader) = oneshot::channel();
+        let (tx_data, mut rx_data) = oneshot::channel();
+        let (tx_button, mut rx_button) = oneshot::channel();
+        pending.push(Pending::Fader {
+            fader: FaderExecutor::new(1, 2),
+            value: 0.5,
+            reply: tx_fader,
+        });
+        pending.push(Pending::Data(Some(tx_data)));
+        pending.push(Pending::Button {
+            button: ButtonExecutor::new(1, 101),
+            state: ButtonState::Pressed,
+            reply: tx_button,
+        });
+
+        // A different value does not confirm the fader
+        let fader = |value| {
+            FaderData::new(
+                FaderExecutor::new(1, 2),
+                "",
+                "",
+                value,
+                false,
+                false,
+                false,
+                false,
+            )
+        };
+        let button = |state| ButtonData::new(ButtonExecutor::new(1, 101), "", "", state);
+        pending.resolve_playbacks(&Ma2Data::new(
+            vec![fader(0.2)],
+            vec![button(ButtonState::Released)],
+        ));
+        assert!(rx_fader.try_recv().is_err());
+        // A report from before the input does not confirm the button
+        assert!(rx_button.try_recv().is_err());
+        pending.resolve_playbacks(&Ma2Data::new(
+            Vec::new(),
+            vec![button(ButtonState::Pressed)],
+        ));
+        assert_eq!(
+            rx_button.try_recv().unwrap().unwrap(),
+            button(ButtonState::Pressed)
+        );
+
+        pending.resolve_playbacks(&Ma2Data::new(vec![fader(0.5001)], Vec::new()));
+        assert_eq!(rx_fader.try_recv().unwrap().unwrap(), fader(0.5001));
+
+        tokio::time::advance(Duration::from_secs(2)).await;
+        pending.expire(Instant::now());
+        let err = rx_data.try_recv().unwrap().unwrap_err();
+        assert!(matches!(*err, Ma2Error::Timeout { .. }));
+        assert!(!pending.data_poll_pending());
+
+        // Polls are answered in order, nobody waits for them
+        pending.push(Pending::Data(None));
+        assert!(pending.data_poll_pending());
+        let mut data = DeskData::new();
+        data.insert(DataKey::Set, "0");
+        pending.resolve_data(&data);
+        assert!(!pending.data_poll_pending());
+    }
+}
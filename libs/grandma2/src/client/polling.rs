@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::Executor;
+
+use super::subscriptions::{PlaybacksPoll, PollPriority};
+
+/// How often one poll has been sent and how often its response contained changes
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollStats {
+    pub page: u8,
+    pub priority: PollPriority,
+    /// The interval the poll is currently sent with
+    pub interval: Duration,
+    pub polls: u64,
+    /// Number of responses that contained at least one changed executor
+    pub changes: u64,
+}
+
+impl PollStats {
+    /// Share of polls that found changes, from `0.0` to `1.0`
+    pub fn change_rate(&self) -> f32 {
+        match self.polls {
+            0 => 0.0,
+            polls => self.changes as f32 / polls as f32,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ScheduledPoll {
+    poll: PlaybacksPoll,
+    interval: Duration,
+    next: Instant,
+    polls: u64,
+    changes: u64,
+}
+
+impl ScheduledPoll {
+    fn current_interval(&self, fast: Duration, idle: Duration) -> Duration {
+        match self.poll.priority {
+            PollPriority::Low => idle,
+            PollPriority::Normal => self.interval,
+            PollPriority::High => fast,
+        }
+    }
+}
+
+/// Decides which polls are due
+///
+/// Normal polls start at the fast interval after user input or changes and double their
+/// interval with every poll up to the idle interval.
+#[derive(Debug)]
+pub(crate) struct Poller {
+    fast: Duration,
+    idle: Duration,
+    polls: Vec<ScheduledPoll>,
+}
+
+impl Poller {
+    pub fn new(fast: Duration, idle: Duration) -> Self {
+        Self {
+            fast,
+            idle: idle.max(fast),
+            polls: Vec::new(),
+        }
+    }
+
+    /// Replaces the polls, schedule and stats of unchanged polls are kept
+    pub fn set_polls(&mut self, polls: &[PlaybacksPoll], now: Instant) {
+        let mut scheduled = std::mem::take(&mut self.polls);
+        self.polls = polls
+            .iter()
+            .map(
+                |poll| match scheduled.iter().position(|old| &old.poll == poll) {
+                    Some(index) => scheduled.swap_remove(index),
+                    None => ScheduledPoll {
+                        poll: poll.clone(),
+                        interval: self.fast,
+                        next: now,
+                        polls: 0,
+                        changes: 0,
+                    },
+                },
+            )
+            .collect();
+    }
+
+    /// Polls that have to be sent at `now`
+    pub fn due(&mut self, now: Instant) -> Vec<PlaybacksPoll> {
+        let mut due = Vec::new();
+        let (fast, idle) = (self.fast, self.idle);
+        for scheduled in self.polls.iter_mut().filter(|poll| poll.next <= now) {
+            let interval = scheduled.current_interval(fast, idle);
+            scheduled.next = now + interval;
+            scheduled.interval = (interval * 2).min(idle);
+            scheduled.polls += 1;
+            due.push(scheduled.poll.clone());
+        }
+        due
+    }
+
+    /// Polls the executors fast again, e.g. after user input
+    pub fn activity(&mut self, executor: Executor, now: Instant) {
+        self.changed_at(&[executor], now);
+    }
+
+    /// Records the executors reported as changed by one response
+    pub fn changed(&mut self, executors: &[Executor], now: Instant) {
+        for scheduled in self.changed_at(executors, now) {
+            scheduled.changes += 1;
+        }
+    }
+
+    /// Speeds up all polls covering one of `executors` except low priority ones, returns all of
+    /// these polls
+    fn changed_at(&mut self, executors: &[Executor], now: Instant) -> Vec<&mut ScheduledPoll> {
+        let fast = self.fast;
+        let mut changed = Vec::new();
+        for scheduled in self.polls.iter_mut() {
+            if executors
+                .iter()
+                .any(|executor| scheduled.poll.covers(*executor))
+            {
+                if scheduled.poll.priority != PollPriority::Low {
+                    scheduled.interval = fast;
+                    scheduled.next = scheduled.next.min(now + fast);
+                }
+                changed.push(scheduled);
+            }
+        }
+        changed
+    }
+
+    /// Polls everything right away, e.g. after reconnecting
+    pub fn reset(&mut self, now: Instant) {
+        for scheduled in self.polls.iter_mut() {
+            scheduled.interval = self.fast;
+            scheduled.next = now;
+        }
+    }
+
+    pub fn stats(&self) -> Vec<PollStats> {
+        self.polls
+            .iter()
+            .map(|scheduled| PollStats {
+                page: scheduled.poll.page,
+                priority: scheduled.poll.priority,
+                interval: scheduled.current_interval(self.fast, self.idle),
+                polls: scheduled.polls,
+                changes: scheduled.changes,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::serde::TYPE_FADER;
+
+    fn poll(page: u8, priority: PollPriority) -> PlaybacksPoll {
+        PlaybacksPoll {
+            priority,
+            ..PlaybacksPoll::single(Executor::new(page, 1), TYPE_FADER)
+        }
+    }
+
+    #[test]
+    fn test_adaptive_interval() {
+        let fast = Duration::from_millis(100);
+        let mut poller = Poller::new(fast, Duration::from_millis(400));
+        let start = Instant::now();
+        poller.set_polls(
+            &[
+                poll(1, PollPriority::Normal),
+                poll(2, PollPriority::High),
+                poll(3, PollPriority::Low),
+            ],
+            start,
+        );
+
+        // Count how often each page is polled within one second without changes
+        let mut counts = [0; 3];
+        for tick in 0..10 {
+            for poll in poller.due(start + fast * tick) {
+                counts[poll.page as usize - 1] += 1;
+            }
+        }
+        // Normal backs off: 0, 100, 300, 700 ms
+        assert_eq!(counts, [4, 10, 3]);
+
+        // A change makes the normal poll fast again
+        let now = start + fast * 10;
+        poller.changed(&[Executor::new(1, 1)], now);
+        assert_eq!(poller.due(now + fast).len(), 2);
+        assert_eq!(poller.due(now + fast * 2).len(), 3);
+        assert_eq!(poller.due(now + fast * 3).len(), 1);
+
+        let stats = poller.stats();
+        assert_eq!(stats[0].changes, 1);
+        assert_eq!(stats[0].polls, 6);
+        assert_eq!(stats[1].change_rate(), 0.0);
+
+        // Unchanged polls keep their stats
+        poller.set_polls(&[poll(1, PollPriority::Normal)], now);
+        assert_eq!(poller.stats()[0].polls, 6);
+
+        // Changes are counted but do not speed up low priority polls
+        let mut poller = Poller::new(fast, Duration::from_millis(400));
+        poller.set_polls(&[poll(3, PollPriority::Low)], start);
+        assert_eq!(poller.due(start).len(), 1);
+        poller.changed(&[Executor::new(3, 1)], start + fast);
+        assert!(poller.due(start + fast * 2).is_empty());
+        assert_eq!(poller.due(start + fast * 4).len(), 1);
+        assert_eq!(poller.stats()[0].changes, 1);
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tungstenite::Message;
+
+use crate::{Ma2Error, Result};
+
+/// A websocket frame as it is stored in a recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Frame {
+    Text(String),
+    Binary(Vec<u8>),
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    Close,
+}
+
+impl Frame {
+    /// `None` for raw frames, they are never sent or received by the client
+    pub fn from_message(msg: &Message) -> Option<Self> {
+        match msg {
+            Message::Text(text) => Some(Frame::Text(text.clone())),
+            Message::Binary(data) => Some(Frame::Binary(data.clone())),
+            Message::Ping(data) => Some(Frame::Ping(data.clone())),
+            Message::Pong(data) => Some(Frame::Pong(data.clone())),
+            Message::Close(_) => Some(Frame::Close),
+            Message::Frame(_) => None,
+        }
+    }
+
+    pub fn into_message(self) -> Message {
+        match self {
+            Frame::Text(text) => Message::Text(text),
+            Frame::Binary(data) => Message::Binary(data),
+            Frame::Ping(data) => Message::Ping(data),
+            Frame::Pong(data) => Message::Pong(data),
+            Frame::Close => Message::Close(None),
+        }
+    }
+}
+
+/// What happened on the websocket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordEvent {
+    /// A new connection to the url has been opened, e.g. after reconnecting
+    Connected(String),
+    Sent(Frame),
+    Received(Frame),
+}
+
+/// A single line of a recording
+///
+/// ``` json
+/// {"time":1718031462107,"received":{"text":"{\"status\":\"server ready\",\"appType\":\"gma2\"}"}}
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// Milliseconds since the unix epoch
+    pub time: u64,
+    #[serde(flatten)]
+    pub event: RecordEvent,
+}
+
+/// The traffic of one client, written by [`crate::GrandMa2Builder::record`]
+///
+/// Can be replayed with [`crate::mock::ReplayServer`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    records: Vec<Record>,
+}
+
+impl Recording {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path).map_err(Ma2Error::RecordingFailed)?;
+        Self::from_reader(BufReader::new(file))
+    }
+
+    /// Reads one [`Record`] per line, empty lines are skipped
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut records = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(Ma2Error::RecordingFailed)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str(&line).map_err(|err| {
+                Ma2Error::DeserialzeError(format!("line {} of recording: {err}", index + 1))
+            })?;
+            records.push(record);
+        }
+        Ok(Self { records })
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// All frames of the desk in the order they have been received
+    pub fn received(&self) -> impl Iterator<Item = &Frame> {
+        self.records
+            .iter()
+            .filter_map(|record| match &record.event {
+                RecordEvent::Received(frame) => Some(frame),
+                _ => None,
+            })
+    }
+}
+
+/// Replaces the password of login requests
+const REDACTED: &str = "redacted";
+
+/// Appends every frame of the client as one JSON line to a file
+///
+/// Lines are buffered and written once the buffer is full or the connection is closed, so the
+/// client is not blocked by the file on every frame. Passwords are not recorded.
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    writer: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Creates the file, an existing recording is overwritten
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::create(path).map_err(Ma2Error::RecordingFailed)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+
+    pub fn record(&mut self, event: RecordEvent) -> Result<()> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as u64);
+        let event = redact(event);
+        let line = serde_json::to_string(&Record { time, event })
+            .map_err(|err| Ma2Error::RecordingFailed(err.into()))?;
+        writeln!(self.writer, "{line}").map_err(|err| Ma2Error::RecordingFailed(err).into())
+    }
+
+    /// Writes all buffered lines to the file
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer
+            .flush()
+            .map_err(|err| Ma2Error::RecordingFailed(err).into())
+    }
+}
+
+/// Replaces the password of a sent login request, other events are kept as they are
+fn redact(event: RecordEvent) -> RecordEvent {
+    let RecordEvent::Sent(Frame::Text(text)) = &event else {
+        return event;
+    };
+    let Ok(Value::Object(mut request)) = serde_json::from_str(text) else {
+        return event;
+    };
+    match request.get_mut("password") {
+        Some(password) => *password = Value::from(REDACTED),
+        None => return event,
+    }
+    RecordEvent::Sent(Frame::Text(Value::Object(request).to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::client::ReceiveMsg;
+
+    #[test]
+    fn test_record_format() {
+        let record = Record {
+            time: 12,
+            event: RecordEvent::Sent(Frame::Text(r#"{"session":0}"#.to_owned())),
+        };
+        let line = serde_json::to_string(&record).unwrap();
+        assert_eq!(line, r#"{"time":12,"sent":{"text":"{\"session\":0}"}}"#);
+
+        let lines = format!(
+            "{line}\n\n{}\n{}\n",
+            r#"{"time":13,"received":"close"}"#, r#"{"time":10,"connected":"ws://desk"}"#
+        );
+        let recording = Recording::from_reader(lines.as_bytes()).unwrap();
+        assert_eq!(recording.records().len(), 3);
+        assert_eq!(recording.records()[0], record);
+        assert_eq!(recording.received().collect::<Vec<_>>(), [&Frame::Close]);
+
+        let err = Recording::from_reader("{}\n".as_bytes()).unwrap_err();
+        assert!(matches!(*err, Ma2Error::DeserialzeError(_)));
+    }
+
+    #[test]
+    fn test_redact_password() {
+        let login = r#"{"requestType":"login","username":"remote","password":"2c18e486683a3db1e645ad8523223b72","session":3}"#;
+        let RecordEvent::Sent(Frame::Text(text)) =
+            redact(RecordEvent::Sent(Frame::Text(login.to_owned())))
+        else {
+            panic!("The event has to be kept");
+        };
+        let request: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(request["password"], REDACTED);
+        assert_eq!(request["username"], "remote");
+
+        let session = RecordEvent::Sent(Frame::Text(r#"{"session":0}"#.to_owned()));
+        assert_eq!(redact(session.clone()), session);
+    }
+
+    /// Every message the desk sent in the recordings in `fixtures/` has to be understood, see
+    /// `fixtures/README.md` for where they come from
+    #[test]
+    fn test_fixtures() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let mut checked = 0;
+        for entry in fs::read_dir(fixtures).unwrap() {
+            let path = entry.unwrap().path();
+            if path
+                .extension()
+                .is_none_or(|extension| extension != "jsonl")
+            {
+                continue;
+            }
+            for frame in Recording::from_file(&path).unwrap().received() {
+                let Frame::Text(text) = frame else {
+                    continue;
+                };
+                let msg = serde_json::from_str(text).and_then(serde_json::from_value::<ReceiveMsg>);
+                if let Err(err) = msg {
+                    panic!("{}: {err}\n{text}", path.display());
+                }
+                checked += 1;
+            }
+        }
+        assert!(checked > 0);
+    }
+}
@@ -2,152 +2,232 @@ use serde::{de, Deserialize, Deserializer};
 use serde_json::Value;
 
 use crate::{
-    types::{ButtonData, FaderData, Ma2Data},
+    types::{
+        BlockButton, BlockFader, ButtonData, CueInfo, ExecutorBlock, FaderData, Ma2Data,
+        PlaybackInfo,
+    },
     Executor,
 };
 
 use super::messages::MaDataResponse;
 
-const TYPE_FADER: u8 = 2;
-const TYPE_BUTTON: u8 = 3;
+pub(crate) const TYPE_FADER: u8 = 2;
+pub(crate) const TYPE_BUTTON: u8 = 3;
 
-impl<'de> Deserialize<'de> for FaderData {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let v = Value::deserialize(deserializer)?;
+/// Reads the executor of a playback item
+///
+/// `iExec` is the zero based index inside the requested item group, `iExecOff` of the group
+/// moves it to the executor range of the group (e.g. `100` for button executors).
+fn parse_executor<E: de::Error>(v: &Value, page: u8, exec_offset: u16) -> Result<Executor, E> {
+    let exec_index = v["iExec"]
+        .as_u64()
+        .ok_or(de::Error::missing_field("iExec"))?;
+    let id = u16::try_from(exec_index + u64::from(exec_offset) + 1)
+        .map_err(|_| de::Error::custom(format!("'{exec_index}' is not a valid executor index")))?;
+    Ok(Executor::new(page, id))
+}
 
-        let executor_str = v["i"]["t"]
-            .as_str()
-            .ok_or(de::Error::missing_field("i.t"))?;
-        let executor = Executor::new(1, executor_str.parse::<u16>().unwrap())
-            .into_fader()
-            .ok_or(de::Error::custom(format!(
-                "'{executor_str}' is not a valid Fader"
-            )))?;
-        let name = v["tt"]["t"]
-            .as_str()
-            .ok_or(de::Error::missing_field("tt.t"))?
-            .to_owned();
-        let color = v["bdC"]
-            .as_str()
-            .ok_or(de::Error::missing_field("bdC"))?
-            .to_owned();
-
-        let exec_blocks = &v["executorBlocks"][0];
-        let value = exec_blocks["fader"]["v"]
-            .as_number()
-            .ok_or(de::Error::missing_field("executorBlocks[0].fader.v"))?
-            .as_f64()
-            .unwrap();
-        let button1 = exec_blocks["button1"]["s"]
-            .as_bool()
-            .ok_or(de::Error::missing_field("executorBlocks[0].button1.s"))?;
-        let button2 = exec_blocks["button2"]["s"]
-            .as_bool()
-            .ok_or(de::Error::missing_field("executorBlocks[0].button2.s"))?;
-        let button3 = exec_blocks["button3"]["s"]
-            .as_bool()
-            .ok_or(de::Error::missing_field("executorBlocks[0].button3.s"))?;
-
-        Ok(FaderData::new(
-            executor,
-            name,
-            color,
-            value as f32,
-            false, // TODO: where is the touched
-            button1,
-            button2,
-            button3,
-        ))
-    }
+/// The desk sends flags either as `true`/`false` or as `1`/`0`
+fn as_flag(v: &Value) -> Option<bool> {
+    v.as_bool().or(v.as_u64().map(|value| value != 0))
 }
 
-impl<'de> Deserialize<'de> for ButtonData {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let v = Value::deserialize(deserializer)?;
+/// Reads an optional colour like `bC` or `tt.c`
+fn parse_color(v: &Value) -> Option<String> {
+    v.as_str().map(str::to_owned)
+}
+
+/// Reads the text of a `{"t": ..., "c": ...}` object
+fn parse_text(v: &Value) -> Option<String> {
+    v["t"].as_str().map(str::to_owned)
+}
+
+/// Reads the information every playback item has, independent of its type
+fn parse_info(v: &Value) -> PlaybackInfo {
+    let cues = v["cues"]["items"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .map(|cue| CueInfo {
+                    text: parse_text(cue).unwrap_or_default(),
+                    color: parse_color(&cue["c"]),
+                    progress: cue["pgs"]["v"].as_f64().map(|progress| progress as f32),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
 
-        let executor_str = v["i"]["t"]
-            .as_str()
-            .ok_or(de::Error::missing_field("i.t"))?;
-        let executor = Executor::new(1, executor_str.parse::<u16>().unwrap())
-            .into_button()
-            .ok_or(de::Error::custom(format!(
-                "'{executor_str}' is not a valid Button"
-            )))?;
-        let name = v["tt"]["t"]
-            .as_str()
-            .ok_or(de::Error::missing_field("tt.t"))?
-            .to_owned();
-        let color = v["bdC"]
-            .as_str()
-            .ok_or(de::Error::missing_field("bdC"))?
-            .to_owned();
-
-        let state = v["isRun"]
-            .as_bool()
-            .ok_or(de::Error::missing_field("executorBlocks[0].button3.s"))?;
-
-        Ok(ButtonData::new(executor, name, color, state))
+    PlaybackInfo {
+        object_type: parse_text(&v["oType"])
+            .map(|object_type| object_type.trim().to_owned())
+            .unwrap_or_default(),
+        object_index: parse_text(&v["oI"])
+            .map(|object_index| object_index.trim().to_owned())
+            .unwrap_or_default(),
+        text_color: parse_color(&v["tt"]["c"]),
+        background_color: parse_color(&v["bC"]),
+        cue_color: parse_color(&v["cues"]["bC"]),
+        cues,
+        combined_items: v["combinedItems"].as_u64().unwrap_or(1) as u16,
+        running: as_flag(&v["isRun"]).unwrap_or(false),
     }
 }
 
-impl<'de> Deserialize<'de> for Ma2Data {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let v = Value::deserialize(deserializer)?;
+fn parse_block_button<E: de::Error>(v: &Value, field: &'static str) -> Result<BlockButton, E> {
+    let state = as_flag(&v["s"]).ok_or(de::Error::missing_field(field))?;
+    Ok(BlockButton {
+        text: parse_text(v).unwrap_or_default(),
+        state: state.into(),
+        color: parse_color(&v["c"]),
+    })
+}
+
+fn parse_block<E: de::Error>(v: &Value) -> Result<ExecutorBlock, E> {
+    let fader = &v["fader"];
+    let value = fader["v"]
+        .as_f64()
+        .ok_or(de::Error::missing_field("executorBlocks.fader.v"))?;
+
+    Ok(ExecutorBlock {
+        button1: parse_block_button(&v["button1"], "executorBlocks.button1.s")?,
+        button2: parse_block_button(&v["button2"], "executorBlocks.button2.s")?,
+        button3: parse_block_button(&v["button3"], "executorBlocks.button3.s")?,
+        fader: BlockFader {
+            label: fader["tt"].as_str().map(str::to_owned),
+            value: value as f32,
+            value_text: fader["vT"].as_str().map(str::to_owned),
+            min: fader["min"].as_f64().unwrap_or(0.0) as f32,
+            max: fader["max"].as_f64().unwrap_or(1.0) as f32,
+            // Not every desk version reports touched faders
+            touched: as_flag(&fader["touched"]).unwrap_or(false),
+        },
+    })
+}
+
+pub(crate) fn parse_fader<E: de::Error>(
+    v: &Value,
+    page: u8,
+    exec_offset: u16,
+) -> Result<FaderData, E> {
+    let executor = parse_executor(v, page, exec_offset)?;
+    let executor = executor
+        .into_fader()
+        .ok_or(de::Error::custom(format!("'{executor}' is not a valid Fader")))?;
+    let name = v["tt"]["t"]
+        .as_str()
+        .ok_or(de::Error::missing_field("tt.t"))?
+        .to_owned();
+    let color = v["bdC"]
+        .as_str()
+        .ok_or(de::Error::missing_field("bdC"))?
+        .to_owned();
+
+    let blocks = v["executorBlocks"]
+        .as_array()
+        .filter(|blocks| !blocks.is_empty())
+        .ok_or(de::Error::missing_field("executorBlocks"))?
+        .iter()
+        .map(parse_block)
+        .collect::<Result<Vec<_>, E>>()?;
+
+    Ok(
+        FaderData::new(executor, name, color, 0.0, false, false, false, false)
+            .with_info(parse_info(v))
+            .with_blocks(blocks),
+    )
+}
+
+pub(crate) fn parse_button<E: de::Error>(
+    v: &Value,
+    page: u8,
+    exec_offset: u16,
+) -> Result<ButtonData, E> {
+    let executor = parse_executor(v, page, exec_offset)?;
+    let executor = executor
+        .into_button()
+        .ok_or(de::Error::custom(format!("'{executor}' is not a valid Button")))?;
+    let name = v["tt"]["t"]
+        .as_str()
+        .ok_or(de::Error::missing_field("tt.t"))?
+        .to_owned();
+    let color = v["bdC"]
+        .as_str()
+        .ok_or(de::Error::missing_field("bdC"))?
+        .to_owned();
+
+    let state = as_flag(&v["isRun"]).ok_or(de::Error::missing_field("isRun"))?;
+
+    Ok(ButtonData::new(executor, name, color, state).with_info(parse_info(v)))
+}
+
+/// Parses the `itemGroups` of a playbacks response that has been sent for `page`
+pub(crate) fn parse_item_groups<E: de::Error>(v: &Value, page: u8) -> Result<Ma2Data, E> {
+    let mut faders = Vec::<FaderData>::new();
+    let mut buttons = Vec::<ButtonData>::new();
 
-        let mut faders = Vec::<FaderData>::new();
-        let mut buttons = Vec::<ButtonData>::new();
+    let items_groups = v
+        .as_array()
+        .ok_or(de::Error::missing_field("itemGroups"))?;
 
-        let items_groups = v.as_array().unwrap();
+    for group in items_groups {
+        let items_type = group["itemsType"]
+            .as_u64()
+            .ok_or(de::Error::missing_field("itemsType"))?;
+        let exec_offset = group["iExecOff"].as_u64().unwrap_or(0) as u16;
+        let item_group_group_groups = group["items"]
+            .as_array()
+            .ok_or(de::Error::missing_field("data.items"))?;
 
-        for group in items_groups {
-            let items_type = group["itemsType"]
-                .as_number()
-                .ok_or(de::Error::missing_field("itemsType"))?
-                .as_u64()
-                .unwrap() as u8;
-            let item_group_group_groups = group["items"]
+        for item_group_groups in item_group_group_groups {
+            let item_groups = item_group_groups
                 .as_array()
-                .ok_or(de::Error::missing_field("data.items"))?;
-
-            for item_group_groups in item_group_group_groups {
-                let item_groups = item_group_groups
-                    .as_array()
-                    .ok_or(de::Error::missing_field("Fader or Button group"))?;
-                for item in item_groups {
-                    match items_type {
-                        TYPE_FADER => {
-                            // Faders
-                            let parsed = FaderData::deserialize(item)
-                                .map_err(|err| de::Error::custom(err))?;
-                            faders.push(parsed);
-                        }
-                        TYPE_BUTTON => {
-                            // Buttons
-                            let parsed = ButtonData::deserialize(item)
-                                .map_err(|err| de::Error::custom(err))?;
-                            buttons.push(parsed);
-                        }
-                        _ => {
-                            return Err(de::Error::custom(format!(
-                                "Invalid value {items_type} for field 'itemsType'"
-                            )))
-                        }
+                .ok_or(de::Error::missing_field("Fader or Button group"))?;
+            for item in item_groups {
+                match items_type as u8 {
+                    TYPE_FADER => faders.push(parse_fader(item, page, exec_offset)?),
+                    TYPE_BUTTON => buttons.push(parse_button(item, page, exec_offset)?),
+                    _ => {
+                        return Err(de::Error::custom(format!(
+                            "Invalid value {items_type} for field 'itemsType'"
+                        )))
                     }
                 }
             }
         }
+    }
+
+    Ok(Ma2Data::new(faders, buttons))
+}
 
-        Ok(Ma2Data::new(faders, buttons))
+/// Deserializes the fields of `Response::Playbacks`
+///
+/// The executors inside `itemGroups` belong to the page given by the sibling field `iPage`.
+pub(crate) fn deserialize_playbacks<'de, D>(
+    deserializer: D,
+) -> Result<(bool, u8, u8, u8, Ma2Data), D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct RawPlaybacks {
+        realtime: bool,
+        world_index: u8,
+        response_sub_type: u8,
+        i_page: u8,
+        item_groups: Value,
     }
+
+    let raw = RawPlaybacks::deserialize(deserializer)?;
+    let item_groups = parse_item_groups(&raw.item_groups, raw.i_page)?;
+    Ok((
+        raw.realtime,
+        raw.world_index,
+        raw.response_sub_type,
+        raw.i_page,
+        item_groups,
+    ))
 }
 
 impl<'de> Deserialize<'de> for MaDataResponse {
@@ -157,33 +237,25 @@ impl<'de> Deserialize<'de> for MaDataResponse {
     {
         let v = Value::deserialize(deserializer)?;
 
-        let arr = v.as_array().ok_or(de::Error::missing_field("i.t"))?;
-
-        let mut set = None;
-        let mut clear = None;
-        let mut solo = None;
-        let mut high = None;
+        // The desk sends a list of objects with a single key each, e.g. `[{"set":"1"}]`
+        let arr = v.as_array().ok_or(de::Error::missing_field("data"))?;
 
+        let mut values = Vec::new();
         for ele in arr {
-            let map = ele.as_object().ok_or(de::Error::missing_field("i.t"))?;
-
-            for (i, j) in map.iter() {
-                match i as &str {
-                    "set" => set = j.as_str(),
-                    "clear" => clear = j.as_str(),
-                    "solo" => solo = j.as_str(),
-                    "high" => high = j.as_str(),
-                    _ => return Err(de::Error::missing_field("i.t")),
-                }
+            let map = ele
+                .as_object()
+                .ok_or(de::Error::custom("Invalid entry in field 'data'"))?;
+
+            for (key, value) in map.iter() {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                values.push((key.clone(), value));
             }
         }
 
-        Ok(MaDataResponse {
-            set: set.ok_or(de::Error::missing_field("set"))?.to_owned(),
-            clear: clear.ok_or(de::Error::missing_field("clear"))?.to_owned(),
-            solo: solo.ok_or(de::Error::missing_field("solo"))?.to_owned(),
-            high: high.ok_or(de::Error::missing_field("high"))?.to_owned(),
-        })
+        Ok(MaDataResponse { values })
     }
 }
 
@@ -193,77 +265,142 @@ mod test {
     use std::str::FromStr;
 
     use super::*;
+    use crate::{ButtonExecutor, ButtonState, ExecutorChanges, FaderButton, FaderExecutor};
 
     #[test]
     fn test_item() {
         let msg = r###"{"i":{"t":"1","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"1","c":"#FFFFFF"},"tt":{"t":"BARS","c":"#FFFFFF"},"bC":"#000000","bdC":"#00FFFF","cues":{"bC":"#003F3F","items":[{"pgs":{}}]},"combinedItems":1,"iExec":0,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#00FFFF","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#00FFFF","leftLED":{},"rightLED":{}},"fader":{"bdC":"#00FFFF","tt":"Mstr","v":1.000,"vT":"100%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#00FFFF","leftLED":{},"rightLED":{}}}]}"###;
-        let msg_parsed: FaderData = serde_json::from_str(msg).unwrap();
-        assert_eq!(
-            FaderData::new(
-                Executor::new(1, 1).into_fader().unwrap(),
-                "BARS",
-                "#00FFFF",
-                1.0,
-                false,
-                false,
-                false,
-                false
-            ),
-            msg_parsed
-        );
+        let msg_value = Value::from_str(msg).unwrap();
+        let msg_parsed: FaderData = parse_fader::<serde_json::Error>(&msg_value, 1, 0).unwrap();
+        let button = |text: &str, color: &str| BlockButton {
+            text: text.to_owned(),
+            state: ButtonState::Released,
+            color: Some(color.to_owned()),
+        };
+        let expected = FaderData::new(
+            Executor::new(1, 1).into_fader().unwrap(),
+            "BARS",
+            "#00FFFF",
+            1.0,
+            false,
+            false,
+            false,
+            false,
+        )
+        .with_info(PlaybackInfo {
+            object_type: "P".to_owned(),
+            object_index: "1".to_owned(),
+            text_color: Some("#FFFFFF".to_owned()),
+            background_color: Some("#000000".to_owned()),
+            cue_color: Some("#003F3F".to_owned()),
+            cues: vec![CueInfo::default()],
+            combined_items: 1,
+            running: false,
+        })
+        .with_blocks(vec![ExecutorBlock {
+            button1: button("Flash", "#FFFF00"),
+            button2: button("Black", "#FFFFFF"),
+            button3: button("SelFix", "#FFFFFF"),
+            fader: BlockFader {
+                label: Some("Mstr".to_owned()),
+                value: 1.0,
+                value_text: Some("100%".to_owned()),
+                min: 0.0,
+                max: 1.0,
+                touched: false,
+            },
+        }]);
+        assert_eq!(expected, msg_parsed);
+        assert_eq!(msg_parsed.info().current_cue(), None);
+
+        let msg_parsed: FaderData = parse_fader::<serde_json::Error>(&msg_value, 3, 0).unwrap();
+        assert_eq!(msg_parsed.get_executer(), &FaderExecutor::new(3, 1));
+        assert_eq!(msg_parsed.name(), "BARS");
+        assert_eq!(msg_parsed.value(), 1.0);
+        assert_eq!(msg_parsed.button(FaderButton::Button1), ButtonState::Released);
+        assert!(!msg_parsed.changes(&expected).any());
+    }
 
+    #[test]
+    fn test_changes() {
+        let msg = r###"{"i":{"t":"61","c":"#FFFFFF"},"oType":{"t":"  LAS","c":"#FFFFFF"},"oI":{"t":"60","c":"#FFFFFF"},"tt":{"t":"STRB_EO","c":"#FFFFFF"},"bC":"#E8A901","bdC":"#FFFFFF","cues":{"bC":"#3F3F3F","items":[{"t":"80.6 BPM","c":"#FFFFFF","pgs":{"v":0.358,"bC":"#808080"}},{"t":"0.0 s","c":"#FFFFFF","pgs":{"v":1.000,"bC":"#808080"}},{"pgs":{"bC":"#808080"}}]},"combinedItems":1,"iExec":0,"isRun":1,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00"},"button2":{"id":1,"t":"Black","s":1,"c":"#FFFFFF"},"fader":{"tt":"Mstr","v":0.500,"vT":"50%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF"}}]}"###;
         let msg_value = Value::from_str(msg).unwrap();
-        let msg_parsed: FaderData = serde_json::from_value(msg_value).unwrap();
+        let previous: FaderData = parse_fader::<serde_json::Error>(&msg_value, 1, 0).unwrap();
+
+        assert_eq!(previous.info().object_type, "LAS");
+        assert_eq!(previous.info().object_index, "60");
+        assert!(previous.info().running);
+        assert_eq!(previous.button(FaderButton::Button2), ButtonState::Pressed);
+        let cue = previous.info().current_cue().unwrap();
+        assert_eq!(cue.text, "80.6 BPM");
+        assert_eq!(cue.progress, Some(0.358));
+        assert_eq!(previous.info().cues.len(), 3);
+        assert!(!previous.changes(&previous).any());
+
+        let msg = msg
+            .replace(r#""v":0.500,"vT":"50%""#, r#""v":0.750,"vT":"75%""#)
+            .replace("80.6 BPM", "90.0 BPM");
+        let msg_value = Value::from_str(&msg).unwrap();
+        let current: FaderData = parse_fader::<serde_json::Error>(&msg_value, 1, 0).unwrap();
         assert_eq!(
-            FaderData::new(
-                Executor::new(1, 1).into_fader().unwrap(),
-                "BARS",
-                "#00FFFF",
-                1.0,
-                false,
-                false,
-                false,
-                false
-            ),
-            msg_parsed
+            current.changes(&previous),
+            ExecutorChanges {
+                value: true,
+                cues: true,
+                ..Default::default()
+            }
         );
     }
 
     #[test]
     fn test_ma_data() {
         let msg = r###"[{"itemsType":2,"cntPages":10000,"items":[[{"i":{"t":"1","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"1","c":"#FFFFFF"},"tt":{"t":"BARS","c":"#FFFFFF"},"bC":"#000000","bdC":"#00FFFF","cues":{"bC":"#003F3F","items":[{"pgs":{}}]},"combinedItems":1,"iExec":0,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#00FFFF","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#00FFFF","leftLED":{},"rightLED":{}},"fader":{"bdC":"#00FFFF","tt":"Mstr","v":1.000,"vT":"100%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#00FFFF","leftLED":{},"rightLED":{}}}]},{"i":{"t":"2","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"13","c":"#FFFFFF"},"tt":{"t":"SSALL","c":"#FFFFFF"},"bC":"#000000","bdC":"#FF7F00","cues":{"bC":"#3F1F00","items":[{"pgs":{}}]},"combinedItems":1,"iExec":1,"isRun":1,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#FF7F00","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#FF7F00","leftLED":{},"rightLED":{}},"fader":{"bdC":"#FF7F00","tt":"Mstr","v":0.000,"vT":"00%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#FF7F00","leftLED":{},"rightLED":{}}}]},{"i":{"t":"3","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"7","c":"#FFFFFF"},"tt":{"t":"shitheads","c":"#FFFFFF"},"bC":"#000000","bdC":"#00FF00","cues":{"bC":"#003F00","items":[{"pgs":{}}]},"combinedItems":1,"iExec":2,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#00FF00","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#00FF00","leftLED":{},"rightLED":{}},"fader":{"bdC":"#00FF00","tt":"Mstr","v":1.000,"vT":"100%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#00FF00","leftLED":{},"rightLED":{}}}]},{"i":{"t":"4","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"5","c":"#FFFFFF"},"tt":{"t":"Tresen","c":"#FFFFFF"},"bC":"#000000","bdC":"#0000FF","cues":{"bC":"#00003F","items":[{"pgs":{}}]},"combinedItems":1,"iExec":3,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#0000FF","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#0000FF","leftLED":{},"rightLED":{}},"fader":{"bdC":"#0000FF","tt":"Mstr","v":1.000,"vT":"100%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#0000FF","leftLED":{},"rightLED":{}}}]},{"i":{"t":"5","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"26","c":"#FFFFFF"},"tt":{"t":"PIX3L","c":"#FFFFFF"},"bC":"#000000","bdC":"#FF7F00","cues":{"bC":"#3F1F00","items":[{"pgs":{}}]},"combinedItems":1,"iExec":4,"isRun":1,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#FF7F00","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#FF7F00","leftLED":{},"rightLED":{}},"fader":{"bdC":"#FF7F00","tt":"Mstr","v":0.000,"vT":"00%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#FF7F00","leftLED":{},"rightLED":{}}}]}],[{"i":{"t":"6","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"10","c":"#FFFFFF"},"tt":{"t":"STRBS","c":"#FFFFFF"},"bC":"#000000","bdC":"#FFFFFF","cues":{"bC":"#3F3F3F","items":[{"pgs":{}}]},"combinedItems":1,"iExec":5,"isRun":1,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#FFFFFF","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#FFFFFF","leftLED":{},"rightLED":{}},"fader":{"bdC":"#FFFFFF","tt":"Mstr","v":0.000,"vT":"00%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#FFFFFF","leftLED":{},"rightLED":{}}}]},{"i":{"t":"7","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"29","c":"#FFFFFF"},"tt":{"t":"HPBAR","c":"#FFFFFF"},"bC":"#000000","bdC":"#00FF7F","cues":{"bC":"#003F1F","items":[{"pgs":{}}]},"combinedItems":1,"iExec":6,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#00FF7F","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#00FF7F","leftLED":{},"rightLED":{}},"fader":{"bdC":"#00FF7F","tt":"Mstr","v":1.000,"vT":"100%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#00FF7F","leftLED":{},"rightLED":{}}}]},{"i":{"t":"8","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"36","c":"#FFFFFF"},"tt":{"t":"pointes","c":"#FFFFFF"},"bC":"#000000","bdC":"#FF0000","cues":{"bC":"#3F0000","items":[{"pgs":{}}]},"combinedItems":1,"iExec":7,"isRun":1,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#FF0000","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#FF0000","leftLED":{},"rightLED":{}},"fader":{"bdC":"#FF0000","tt":"Mstr","v":0.000,"vT":"00%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#FF0000","leftLED":{},"rightLED":{}}}]},{"i":{"t":"9","c":"#FFFFFF"},"oType":{"t":"  ","c":"#FFFFFF"},"oI":{"t":"","c":"#FFFFFF"},"tt":{"t":"Grand","c":"#FFFFFF"},"bC":"#E8A901","bdC":"#FF007F","cues":{"bC":"#3F001F","items":[{"t":"100%","c":"#FFFFFF","pgs":{}}]},"combinedItems":1,"iExec":8,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Black","s":false,"c":"#FFFF00","bdC":"#FF007F","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#FF007F","leftLED":{},"rightLED":{}},"fader":{"bdC":"#FF007F","tt":"Grand","v":1.000,"vT":"100%","min":0.000,"max":1.000},"button3":{"id":2,"t":"Empty","s":false,"c":"#FFFFFF","bdC":"#FF007F","leftLED":{},"rightLED":{}}}]},{"i":{"t":"10","c":"#000000"},"oType":{"t":"","c":"#FFFFFF"},"oI":{"t":"","c":"#FFFFFF"},"tt":{"t":"","c":"#FFFFFF"},"bC":"#404040","bdC":"#404040","cues":{},"combinedItems":1,"iExec":9,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Empty","s":false,"c":"#808080","bdC":"#404040","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Empty","s":false,"c":"#808080","bdC":"#404040","leftLED":{},"rightLED":{}},"fader":{"bdC":"#404040","v":0.000,"vT":"","min":0.000,"max":1.000},"button3":{"id":2,"t":"Empty","s":false,"c":"#808080","bdC":"#404040","leftLED":{},"rightLED":{}}}]}],[{"i":{"t":"11","c":"#000000"},"oType":{"t":""},"oI":{"t":""},"tt":{"t":""},"bC":"#404040","bdC":"#3D3D3D","cues":{},"combinedItems":1,"iExec":10,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"fader":{"v":0.000,"min":0.000,"max":0.000},"button3":{"id":2,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}}}]},{"i":{"t":"12","c":"#000000"},"oType":{"t":""},"oI":{"t":""},"tt":{"t":""},"bC":"#404040","bdC":"#3D3D3D","cues":{},"combinedItems":1,"iExec":11,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"fader":{"v":0.000,"min":0.000,"max":0.000},"button3":{"id":2,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}}}]},{"i":{"t":"13","c":"#000000"},"oType":{"t":""},"oI":{"t":""},"tt":{"t":""},"bC":"#404040","bdC":"#3D3D3D","cues":{},"combinedItems":1,"iExec":12,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"fader":{"v":0.000,"min":0.000,"max":0.000},"button3":{"id":2,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}}}]},{"i":{"t":"14","c":"#000000"},"oType":{"t":""},"oI":{"t":""},"tt":{"t":""},"bC":"#404040","bdC":"#3D3D3D","cues":{},"combinedItems":1,"iExec":13,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"fader":{"v":0.000,"min":0.000,"max":0.000},"button3":{"id":2,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}}}]},{"i":{"t":"15","c":"#000000"},"oType":{"t":""},"oI":{"t":""},"tt":{"t":""},"bC":"#404040","bdC":"#3D3D3D","cues":{},"combinedItems":1,"iExec":14,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}},"fader":{"v":0.000,"min":0.000,"max":0.000},"button3":{"id":2,"t":"Empty","s":false,"c":"#808080","leftLED":{},"rightLED":{}}}]}]]}]"###;
-        let _msg_parsed: Ma2Data = serde_json::from_str(msg).unwrap();
+        let msg_value = Value::from_str(msg).unwrap();
+        let _msg_parsed: Ma2Data = parse_item_groups::<serde_json::Error>(&msg_value, 1).unwrap();
 
         let msg = r###"[{"itemsType":2,"cntPages":10000,"items":[[{"i":{"t":"1","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"1","c":"#FFFFFF"},"tt":{"t":"BARS","c":"#FFFFFF"},"bC":"#000000","bdC":"#00FFFF","cues":{"bC":"#003F3F","items":[{"pgs":{}}]},"combinedItems":1,"iExec":0,"isRun":0,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#00FFFF","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#00FFFF","leftLED":{},"rightLED":{}},"fader":{"bdC":"#00FFFF","tt":"Mstr","v":1.000,"vT":"100%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#00FFFF","leftLED":{},"rightLED":{}}}]},{"i":{"t":"2","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"13","c":"#FFFFFF"},"tt":{"t":"SSALL","c":"#FFFFFF"},"bC":"#000000","bdC":"#FF7F00","cues":{"bC":"#3F1F00","items":[{"pgs":{}}]},"combinedItems":1,"iExec":1,"isRun":1,"executorBlocks":[{"button1":{"id":0,"t":"Flash","s":false,"c":"#FFFF00","bdC":"#FF7F00","leftLED":{},"rightLED":{}},"button2":{"id":1,"t":"Black","s":false,"c":"#FFFFFF","bdC":"#FF7F00","leftLED":{},"rightLED":{}},"fader":{"bdC":"#FF7F00","tt":"Mstr","v":0.000,"vT":"00%","min":0.000,"max":1.000},"button3":{"id":2,"t":"SelFix","s":false,"c":"#FFFFFF","bdC":"#FF7F00","leftLED":{},"rightLED":{}}}]}]]}]"###;
-        let msg_parsed: Ma2Data = serde_json::from_str(msg).unwrap();
+        let msg_value = Value::from_str(msg).unwrap();
+        let msg_parsed: Ma2Data = parse_item_groups::<serde_json::Error>(&msg_value, 1).unwrap();
 
+        let faders: Vec<(FaderExecutor, &str, &str, f32)> = msg_parsed
+            .faders()
+            .iter()
+            .map(|fader| (*fader.get_executer(), fader.name(), fader.color(), fader.value()))
+            .collect();
         assert_eq!(
-            Ma2Data {
-                fader_data: vec![
-                    FaderData::new(
-                        Executor::new(1, 1).into_fader().unwrap(),
-                        "BARS",
-                        "#00FFFF",
-                        1.0,
-                        false,
-                        false,
-                        false,
-                        false
-                    ),
-                    FaderData::new(
-                        Executor::new(1, 2).into_fader().unwrap(),
-                        "SSALL",
-                        "#FF7F00",
-                        0.0,
-                        false,
-                        false,
-                        false,
-                        false
-                    ),
-                ],
-                button_data: Vec::new(),
-            },
-            msg_parsed
+            vec![
+                (FaderExecutor::new(1, 1), "BARS", "#00FFFF", 1.0),
+                (FaderExecutor::new(1, 2), "SSALL", "#FF7F00", 0.0),
+            ],
+            faders
+        );
+        assert!(msg_parsed.buttons().is_empty());
+        assert!(msg_parsed.faders()[1].info().running);
+    }
+
+    #[test]
+    fn test_buttons() {
+        let msg = r###"[{"itemsType":3,"iExecOff":100,"cntPages":10000,"items":[[{"i":{"t":"1","c":"#FFFFFF"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"1","c":"#FFFFFF"},"tt":{"t":"Edit BARS","c":"#FFFFFF"},"bC":"#800000","bdC":"#00FFFF","cues":{"bC":"#003F3F","items":[{"pgs":{}}]},"combinedItems":1,"iExec":0,"isRun":0},{"i":{"t":"2","c":"#C0C0C0"},"oType":{"t":" P","c":"#FFFFFF"},"oI":{"t":"13","c":"#FFFFFF"},"tt":{"t":"SSALL","c":"#FFFFFF"},"bC":"#000000","bdC":"#FF7F00","cues":{"bC":"#3F1F00","items":[{"pgs":{}}]},"combinedItems":1,"iExec":1,"isRun":1}]]}]"###;
+        let msg_value = Value::from_str(msg).unwrap();
+        let msg_parsed: Ma2Data = parse_item_groups::<serde_json::Error>(&msg_value, 2).unwrap();
+
+        let buttons: Vec<(ButtonExecutor, &str, &str, ButtonState)> = msg_parsed
+            .buttons()
+            .iter()
+            .map(|button| (*button.get_executer(), button.name(), button.color(), button.state()))
+            .collect();
+        assert_eq!(
+            vec![
+                (ButtonExecutor::new(2, 101), "Edit BARS", "#00FFFF", ButtonState::Released),
+                (ButtonExecutor::new(2, 102), "SSALL", "#FF7F00", ButtonState::Pressed),
+            ],
+            buttons
+        );
+        assert!(msg_parsed.faders().is_empty());
+        assert_eq!(msg_parsed.buttons()[1].info().object_index, "13");
+        assert_eq!(
+            msg_parsed.buttons()[1].info().background_color.as_deref(),
+            Some("#000000")
         );
     }
 }
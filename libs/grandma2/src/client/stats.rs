@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+/// Health of the connection to the desk, see [`crate::GrandMa2::stats`]
+///
+/// Counters start when the client is created and are kept when reconnecting.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientStats {
+    /// Websocket frames sent to the desk
+    pub messages_sent: u64,
+    /// Websocket frames received from the desk
+    pub messages_received: u64,
+    /// Messages of the desk that could not be deserialized
+    pub deserialize_failures: u64,
+    /// Sessions renewed because the desk stops answering after 10 responses
+    pub session_renewals: u64,
+    pub login_attempts: u64,
+    /// Connections that have been reopened after the connection was lost
+    pub reconnects: u64,
+    /// Playback polls the desk answered
+    pub polls_answered: u64,
+    /// Round trip time of the last answered playback poll
+    pub poll_latency: Option<Duration>,
+    /// Mean round trip time of all answered playback polls
+    pub mean_poll_latency: Option<Duration>,
+    pub max_poll_latency: Option<Duration>,
+}
+
+impl ClientStats {
+    pub(crate) fn record_poll_latency(&mut self, latency: Duration) {
+        // Incremental mean, summing up all latencies could overflow
+        let mean = self.mean_poll_latency.unwrap_or_default().as_secs_f64();
+        self.polls_answered += 1;
+        let mean = mean + (latency.as_secs_f64() - mean) / self.polls_answered as f64;
+        self.poll_latency = Some(latency);
+        self.mean_poll_latency = Some(Duration::from_secs_f64(mean.max(0.0)));
+        self.max_poll_latency = self.max_poll_latency.max(Some(latency));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_poll_latency() {
+        let mut stats = ClientStats::default();
+        for millis in [10, 30, 20] {
+            stats.record_poll_latency(Duration::from_millis(millis));
+        }
+        assert_eq!(stats.polls_answered, 3);
+        assert_eq!(stats.poll_latency, Some(Duration::from_millis(20)));
+        let mean = stats.mean_poll_latency.unwrap();
+        assert_eq!(mean.as_micros(), 20_000);
+        assert_eq!(stats.max_poll_latency, Some(Duration::from_millis(30)));
+
+        // Counts beyond 32 bits keep the mean
+        stats.polls_answered = u32::MAX as u64;
+        stats.record_poll_latency(Duration::from_millis(20));
+        assert_eq!(stats.polls_answered, u32::MAX as u64 + 1);
+        assert_eq!(stats.mean_poll_latency.unwrap().as_micros(), 20_000);
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    types::{ButtonRange, FaderRange},
+    Executor,
+};
+
+use super::serde::{TYPE_BUTTON, TYPE_FADER};
+
+/// Handle of a subscription, used to unsubscribe again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubscriptionId(u64);
+
+impl SubscriptionId {
+    pub(crate) fn new(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+/// How often a subscription is polled, see [`crate::GrandMa2::set_poll_priority`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum PollPriority {
+    /// Polled at the idle interval, even while the desk is busy
+    Low,
+    /// Polled fast after changes or user input and slower while nothing changes
+    #[default]
+    Normal,
+    /// Always polled at the poll interval
+    High,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Subscription {
+    Button(ButtonRange),
+    Fader(FaderRange),
+}
+
+impl Subscription {
+    /// Page, items type and the zero based executor indices `start..end` covered by this subscription
+    fn span(&self) -> (u8, u8, u16, u16) {
+        let (page, items_type, start, count) = match self {
+            Subscription::Button(range) => (
+                range.page(),
+                TYPE_BUTTON,
+                range.start_button.id(),
+                range.num_buttons,
+            ),
+            Subscription::Fader(range) => (
+                range.page(),
+                TYPE_FADER,
+                range.start_fader.id(),
+                range.num_faders,
+            ),
+        };
+        let start = start.saturating_sub(1);
+        (page, items_type, start, start + count)
+    }
+}
+
+/// A single `playbacks` request covering all subscribed executors of one page and priority
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlaybacksPoll {
+    pub page: u8,
+    pub priority: PollPriority,
+    pub start_index: Vec<u16>,
+    pub items_count: Vec<u16>,
+    pub items_type: Vec<u8>,
+}
+
+impl PlaybacksPoll {
+    /// Poll of a single executor, e.g. to confirm an input
+    pub fn single(executor: Executor, items_type: u8) -> Self {
+        Self {
+            page: executor.page(),
+            priority: PollPriority::High,
+            start_index: vec![executor.id().saturating_sub(1)],
+            items_count: vec![1],
+            items_type: vec![items_type],
+        }
+    }
+
+    /// Whether the response to this poll contains `executor`
+    pub fn covers(&self, executor: Executor) -> bool {
+        let index = executor.id().saturating_sub(1);
+        executor.page() == self.page
+            && self
+                .start_index
+                .iter()
+                .zip(&self.items_count)
+                .any(|(start, count)| (*start..start + count).contains(&index))
+    }
+}
+
+/// Registry of all button and fader subscriptions
+///
+/// Overlapping and adjacent ranges of the same priority are merged, so every page is polled
+/// with a single request per priority.
+#[derive(Debug, Default)]
+pub(crate) struct Subscriptions {
+    subscriptions: BTreeMap<SubscriptionId, Subscription>,
+    priorities: BTreeMap<SubscriptionId, PollPriority>,
+    polls: Vec<PlaybacksPoll>,
+}
+
+impl Subscriptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, id: SubscriptionId, subscription: Subscription) {
+        self.subscriptions.insert(id, subscription);
+        self.update_polls();
+    }
+
+    /// Removes a subscription, returns `false` if it did not exist
+    pub fn remove(&mut self, id: SubscriptionId) -> bool {
+        let removed = self.subscriptions.remove(&id).is_some();
+        self.priorities.remove(&id);
+        self.update_polls();
+        removed
+    }
+
+    /// Changes the priority of a subscription, returns `false` if it does not exist
+    pub fn set_priority(&mut self, id: SubscriptionId, priority: PollPriority) -> bool {
+        if !self.subscriptions.contains_key(&id) {
+            return false;
+        }
+        self.priorities.insert(id, priority);
+        self.update_polls();
+        true
+    }
+
+    pub fn polls(&self) -> &[PlaybacksPoll] {
+        &self.polls
+    }
+
+    fn update_polls(&mut self) {
+        // Sorting by page, priority, type and start puts mergeable spans next to each other
+        let mut spans: Vec<(u8, PollPriority, u8, u16, u16)> = self
+            .subscriptions
+            .iter()
+            .map(|(id, subscription)| {
+                let (page, items_type, start, end) = subscription.span();
+                let priority = self.priorities.get(id).copied().unwrap_or_default();
+                (page, priority, items_type, start, end)
+            })
+            .collect();
+        spans.sort_unstable();
+
+        let mut merged: Vec<(u8, PollPriority, u8, u16, u16)> = Vec::new();
+        for span in spans {
+            match merged.last_mut() {
+                Some(last)
+                    if last.0 == span.0
+                        && last.1 == span.1
+                        && last.2 == span.2
+                        && span.3 <= last.4 =>
+                {
+                    last.4 = last.4.max(span.4);
+                }
+                _ => merged.push(span),
+            }
+        }
+
+        let mut polls: Vec<PlaybacksPoll> = Vec::new();
+        for (page, priority, items_type, start, end) in merged {
+            let poll = match polls.last_mut() {
+                Some(poll) if poll.page == page && poll.priority == priority => poll,
+                _ => {
+                    polls.push(PlaybacksPoll {
+                        page,
+                        priority,
+                        start_index: Vec::new(),
+                        items_count: Vec::new(),
+                        items_type: Vec::new(),
+                    });
+                    polls.last_mut().unwrap()
+                }
+            };
+            poll.start_index.push(start);
+            poll.items_count.push(end - start);
+            poll.items_type.push(items_type);
+        }
+        self.polls = polls;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ButtonExecutor, FaderExecutor};
+
+    fn faders(page: u8, start: u16, end: u16) -> Subscription {
+        Subscription::Fader(
+            FaderRange::from(
+                FaderExecutor::new(page, start),
+                FaderExecutor::new(page, end),
+            )
+            .unwrap(),
+        )
+    }
+
+    fn buttons(page: u8, start: u16, end: u16) -> Subscription {
+        Subscription::Button(
+            ButtonRange::from(
+                ButtonExecutor::new(page, start),
+                ButtonExecutor::new(page, end),
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut subscriptions = Subscriptions::new();
+        subscriptions.insert(SubscriptionId::new(0), faders(1, 1, 8));
+        subscriptions.insert(SubscriptionId::new(1), faders(1, 5, 10));
+        subscriptions.insert(SubscriptionId::new(2), faders(1, 11, 15));
+        subscriptions.insert(SubscriptionId::new(3), faders(1, 20, 20));
+        subscriptions.insert(SubscriptionId::new(4), buttons(1, 101, 110));
+        subscriptions.insert(SubscriptionId::new(5), faders(2, 1, 5));
+
+        assert_eq!(
+            subscriptions.polls(),
+            &[
+                PlaybacksPoll {
+                    page: 1,
+                    priority: PollPriority::Normal,
+                    start_index: vec![0, 19, 100],
+                    items_count: vec![15, 1, 10],
+                    items_type: vec![TYPE_FADER, TYPE_FADER, TYPE_BUTTON],
+                },
+                PlaybacksPoll {
+                    page: 2,
+                    priority: PollPriority::Normal,
+                    start_index: vec![0],
+                    items_count: vec![5],
+                    items_type: vec![TYPE_FADER],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut subscriptions = Subscriptions::new();
+        subscriptions.insert(SubscriptionId::new(0), faders(1, 1, 8));
+        subscriptions.insert(SubscriptionId::new(1), faders(1, 1, 4));
+
+        assert!(subscriptions.remove(SubscriptionId::new(0)));
+        assert!(!subscriptions.remove(SubscriptionId::new(0)));
+        assert_eq!(
+            subscriptions.polls(),
+            &[PlaybacksPoll {
+                page: 1,
+                priority: PollPriority::Normal,
+                start_index: vec![0],
+                items_count: vec![4],
+                items_type: vec![TYPE_FADER],
+            }]
+        );
+
+        assert!(subscriptions.remove(SubscriptionId::new(1)));
+        assert!(subscriptions.polls().is_empty());
+    }
+
+    #[test]
+    fn test_priorities() {
+        let mut subscriptions = Subscriptions::new();
+        subscriptions.insert(SubscriptionId::new(0), faders(1, 1, 5));
+        subscriptions.insert(SubscriptionId::new(1), faders(1, 6, 10));
+        assert_eq!(subscriptions.polls().len(), 1);
+
+        // Ranges of different priorities are polled separately
+        assert!(subscriptions.set_priority(SubscriptionId::new(1), PollPriority::High));
+        assert!(!subscriptions.set_priority(SubscriptionId::new(2), PollPriority::High));
+        let polls = subscriptions.polls();
+        assert_eq!(polls.len(), 2);
+        assert_eq!(polls[0].priority, PollPriority::Normal);
+        assert_eq!(polls[1].priority, PollPriority::High);
+        assert!(polls[1].covers(FaderExecutor::new(1, 6).executor()));
+        assert!(!polls[1].covers(FaderExecutor::new(1, 5).executor()));
+        assert!(!polls[1].covers(FaderExecutor::new(2, 6).executor()));
+    }
+}
@@ -0,0 +1,70 @@
+use std::{fs, path::Path, sync::Arc};
+
+use rustls::{crypto::ring, ClientConfig, RootCertStore};
+use tokio_tungstenite::Connector;
+
+use crate::{Ma2Error, Result};
+
+/// TLS settings for `wss://` connections
+///
+/// Without one, certificates are verified against the Mozilla CA roots. Desks are usually
+/// reached through a proxy with a self-signed certificate, so a `TlsConfig` trusts only the
+/// given CA roots.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    client_config: Arc<ClientConfig>,
+}
+
+impl TlsConfig {
+    /// Trusts all CA certificates of a PEM file
+    pub fn from_pem_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let pem = fs::read(path).map_err(|err| {
+            Ma2Error::InvalidTlsConfig(format!("Cannot read {}: {err}", path.display()))
+        })?;
+        Self::from_pem(&pem)
+    }
+
+    /// Trusts all CA certificates of PEM encoded `pem`
+    pub fn from_pem(mut pem: &[u8]) -> Result<Self> {
+        let mut roots = RootCertStore::empty();
+        for cert in rustls_pemfile::certs(&mut pem) {
+            let cert = cert.map_err(|err| Ma2Error::InvalidTlsConfig(err.to_string()))?;
+            roots
+                .add(cert)
+                .map_err(|err| Ma2Error::InvalidTlsConfig(err.to_string()))?;
+        }
+        if roots.is_empty() {
+            return Err(Ma2Error::InvalidTlsConfig("No certificate found".to_owned()).into());
+        }
+
+        let client_config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|err| Ma2Error::InvalidTlsConfig(err.to_string()))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Ok(Self::from_rustls(Arc::new(client_config)))
+    }
+
+    /// Uses a rustls configuration as is, e.g. to add client certificates
+    pub fn from_rustls(client_config: Arc<ClientConfig>) -> Self {
+        Self { client_config }
+    }
+
+    pub(crate) fn connector(&self) -> Connector {
+        Connector::Rustls(self.client_config.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_invalid_pem() {
+        let err = TlsConfig::from_pem(b"not a certificate").unwrap_err();
+        assert!(matches!(*err, Ma2Error::InvalidTlsConfig(_)));
+        let err = TlsConfig::from_pem_file("/does/not/exist.pem").unwrap_err();
+        assert!(matches!(*err, Ma2Error::InvalidTlsConfig(_)));
+    }
+}
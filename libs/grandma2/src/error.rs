@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use crate::interface::MaRequest;
 use crate::client::{ReceiveMsg, SendMsg};
@@ -15,19 +15,39 @@ pub enum Ma2Error {
         tungstenite_error: tungstenite::Error,
     },
     WebsocketNotYetConnected,
+    /// The CA roots for `wss://` connections could not be loaded
+    InvalidTlsConfig(String),
+    ConnectionLost,
+    ReconnectFailed {
+        url: String,
+        attempts: u32,
+    },
     // Ma Connection Errors
     ConnectedButInvalidSessionId,
     WebRemoteDisabled,
     WebsocketFailedToSend(tungstenite::Error),
+    /// A websocket frame the web remote does not send, e.g. a binary frame
+    UnexpectedFrame(String),
+    /// A plain text message of the desk
+    DeskMessage(String),
     // Messages/Handler not implemented
     CouldNotDeserializeReceiveMsg(String, Box<serde_json::Error>),
     CouldNotSerializeSendMsg(SendMsg),
     MessageHandlerNotImplemented(ReceiveMsg),
     RequestHandlerNotImplemented(MaRequest),
+    /// A recording of the websocket traffic could not be read or written
+    RecordingFailed(std::io::Error),
+    /// The background thread of [`crate::blocking::GrandMa2`] could not be started
+    FailedToStartRuntime(std::io::Error),
     // Interface Channels closed
     NotYetConnected,
     RequestChannelClosed,
     EventChannelClosed,
+    /// The desk did not answer the request in time
+    Timeout {
+        request: String,
+        timeout: Duration,
+    },
     // Ma2 errors
     LoginFailed {
         username: String,
@@ -40,8 +60,49 @@ pub enum Ma2Error {
     SerializeError(SendMsg),
     DeserialzeError(String),
     // Types
+    /// A string that is not an executor like `1.101`
+    InvalidExecutor(String),
     ButtonIdOutOfRange(Executor),
     FaderIdOutOfRange(Executor),
+    /// A cue number that is negative, infinite or NaN
+    InvalidCueNumber(f32),
+}
+
+impl Ma2Error {
+    /// Whether the error has been caused by a broken websocket connection
+    pub fn is_connection_lost(&self) -> bool {
+        matches!(
+            self,
+            Ma2Error::ConnectionLost | Ma2Error::WebsocketFailedToSend(_)
+        )
+    }
+
+    /// Whether the client has to stop after this error
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            Ma2Error::ReconnectFailed { .. }
+                | Ma2Error::WebRemoteDisabled
+                | Ma2Error::LoginFailed { .. }
+                | Ma2Error::RequestChannelClosed
+                | Ma2Error::EventChannelClosed
+        )
+    }
+
+    /// Whether the error has been caused by a message the client does not understand
+    ///
+    /// Such messages are usually quirks of a firmware version and are reported as
+    /// [`MaEvent::Warning`](crate::interface::MaEvent::Warning).
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            self,
+            Ma2Error::UnexpectedFrame(_)
+                | Ma2Error::DeskMessage(_)
+                | Ma2Error::CouldNotDeserializeReceiveMsg(..)
+                | Ma2Error::MessageHandlerNotImplemented(_)
+                | Ma2Error::ConnectedButInvalidSessionId
+        )
+    }
 }
 
 impl fmt::Display for Ma2Error {
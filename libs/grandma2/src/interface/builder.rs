@@ -0,0 +1,119 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::client::Backoff;
+#[cfg(feature = "tls")]
+use crate::client::TlsConfig;
+
+use super::GrandMa2;
+
+/// Configures a [`GrandMa2`] connection, see [`GrandMa2::builder`]
+///
+/// ``` rust,ignore
+/// let grandma = GrandMa2::builder("wss://desk:8443", "remote", "remote")
+///     .poll_interval(Duration::from_millis(250))
+///     .tls(TlsConfig::from_pem_file("desk-ca.pem")?)
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct GrandMa2Builder {
+    grandma: GrandMa2,
+}
+
+impl GrandMa2Builder {
+    pub(crate) fn new(grandma: GrandMa2) -> Self {
+        Self { grandma }
+    }
+
+    /// How the client reconnects, `None` disables reconnecting
+    pub fn reconnect(mut self, backoff: Option<Backoff>) -> Self {
+        self.grandma.config.backoff = backoff;
+        self
+    }
+
+    /// How long to wait for the desk to answer a request, defaults to 2 s
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.grandma.config.request_timeout = timeout;
+        self
+    }
+
+    /// How often subscribed executors are polled after changes or user input, defaults to
+    /// 100 ms
+    ///
+    /// Large executor ranges produce large responses, a longer interval eases the load on
+    /// the desk.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.grandma.config.poll_interval = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    /// Longest interval executors are polled with while nothing changes, defaults to 1 s
+    ///
+    /// Setting it to the poll interval disables adaptive polling.
+    pub fn idle_poll_interval(mut self, interval: Duration) -> Self {
+        self.grandma.config.idle_poll_interval = interval;
+        self
+    }
+
+    /// Number of responses after which the session is renewed, defaults to 9
+    ///
+    /// The desk stops answering after 10 responses without renewal.
+    pub fn session_renewal(mut self, responses: u8) -> Self {
+        self.grandma.config.session_renewal = responses.max(1);
+        self
+    }
+
+    /// `maxRequests` sent with every poll, defaults to 1
+    pub fn max_requests(mut self, max_requests: u16) -> Self {
+        self.grandma.config.max_requests = max_requests;
+        self
+    }
+
+    /// Shortest interval between two inputs of the same fader, defaults to 20 ms
+    ///
+    /// Faster moves are coalesced, only the latest value is sent. `Duration::ZERO` sends every
+    /// value that differs from the last known one.
+    pub fn fader_interval(mut self, interval: Duration) -> Self {
+        self.grandma.config.fader_interval = interval;
+        self
+    }
+
+    /// Report [`GrandMa2::stats`] as [`crate::interface::MaEvent::Stats`] every `interval`,
+    /// disabled by default
+    pub fn stats_interval(mut self, interval: Duration) -> Self {
+        self.grandma.config.stats_interval = Some(interval);
+        self
+    }
+
+    /// How long opening the websocket may take, defaults to 5 s
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.grandma.config.connect_timeout = timeout;
+        self
+    }
+
+    /// Number of events every event stream buffers, see [`GrandMa2::set_event_capacity`]
+    pub fn event_capacity(mut self, capacity: usize) -> Self {
+        self.grandma.set_event_capacity(capacity);
+        self
+    }
+
+    /// Verify `wss://` connections with the given CA roots instead of the Mozilla ones
+    #[cfg(feature = "tls")]
+    pub fn tls(mut self, tls: TlsConfig) -> Self {
+        self.grandma.config.tls = Some(tls);
+        self
+    }
+
+    /// Record every websocket frame with a timestamp to a JSON lines file
+    ///
+    /// The file is overwritten on every connect and written completely once the connection is
+    /// closed. The password of the login is redacted. Read it with [`crate::Recording`] or
+    /// replay it with [`crate::mock::ReplayServer`].
+    pub fn record(mut self, path: impl Into<PathBuf>) -> Self {
+        self.grandma.config.record = Some(path.into());
+        self
+    }
+
+    pub fn build(self) -> GrandMa2 {
+        self.grandma
+    }
+}
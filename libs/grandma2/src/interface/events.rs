@@ -0,0 +1,215 @@
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{Executor, Ma2Error, Result};
+
+use super::MaEvent;
+
+/// Number of events a subscriber may fall behind before it misses events
+pub(crate) const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
+/// The kind of a [`MaEvent`], used to filter event streams
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// [`MaEvent::Disconnected`], [`MaEvent::Reconnected`] and [`MaEvent::LoginSuccessful`]
+    Connection,
+    Page,
+    Fader,
+    Button,
+    Cue,
+    Data,
+    Stats,
+    Warning,
+    Error,
+}
+
+impl EventKind {
+    /// Kind of `event`, `None` for [`MaEvent::Lagged`] which is never filtered
+    pub fn of(event: &MaEvent) -> Option<Self> {
+        match event {
+            MaEvent::Disconnected | MaEvent::Reconnected | MaEvent::LoginSuccessful(_) => {
+                Some(EventKind::Connection)
+            }
+            MaEvent::PageChanged(_) => Some(EventKind::Page),
+            MaEvent::FaderChanged(_) => Some(EventKind::Fader),
+            MaEvent::ButtonChanged(_) => Some(EventKind::Button),
+            MaEvent::CueChanged(_) => Some(EventKind::Cue),
+            MaEvent::DataChanged(_) => Some(EventKind::Data),
+            MaEvent::Stats(_) => Some(EventKind::Stats),
+            MaEvent::Warning(_) => Some(EventKind::Warning),
+            MaEvent::Error(_) => Some(EventKind::Error),
+            MaEvent::Lagged(_) => None,
+        }
+    }
+}
+
+/// Selects the events delivered to an [`EventStream`]
+///
+/// An empty filter lets all events pass. Executors only restrict fader, button and cue events,
+/// events without an executor are selected by their kind alone.
+///
+/// ``` rust,ignore
+/// let filter = EventFilter::new()
+///     .kind(EventKind::Fader)
+///     .executor(FaderExecutor::new(1, 1));
+/// let mut events = grandma.subscribe_filtered(filter)?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    kinds: Vec<EventKind>,
+    executors: Vec<Executor>,
+}
+
+impl EventFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also let events of `kind` pass
+    pub fn kind(mut self, kind: EventKind) -> Self {
+        self.kinds.push(kind);
+        self
+    }
+
+    /// Also let fader, button and cue events of `executor` pass
+    pub fn executor(mut self, executor: impl Into<Executor>) -> Self {
+        self.executors.push(executor.into());
+        self
+    }
+
+    pub fn matches(&self, event: &MaEvent) -> bool {
+        let Some(kind) = EventKind::of(event) else {
+            return true;
+        };
+        if !self.kinds.is_empty() && !self.kinds.contains(&kind) {
+            return false;
+        }
+
+        let executor = match event {
+            MaEvent::FaderChanged(data) => data.get_executer().executor(),
+            MaEvent::ButtonChanged(data) => data.get_executer().executor(),
+            MaEvent::CueChanged(status) => status.executor,
+            _ => return true,
+        };
+        self.executors.is_empty() || self.executors.contains(&executor)
+    }
+}
+
+/// An independent stream of desk events, see [`crate::GrandMa2::subscribe`]
+///
+/// Every stream has its own bounded buffer. A stream that falls behind misses the oldest
+/// events and reports how many with [`MaEvent::Lagged`].
+#[derive(Debug)]
+pub struct EventStream {
+    rx_event: broadcast::Receiver<MaEvent>,
+    filter: EventFilter,
+}
+
+impl EventStream {
+    pub(crate) fn new(rx_event: broadcast::Receiver<MaEvent>, filter: EventFilter) -> Self {
+        Self { rx_event, filter }
+    }
+
+    /// Waits for the next event matching the filter
+    ///
+    /// Fails with [`Ma2Error::EventChannelClosed`] once the client has stopped.
+    pub async fn recv(&mut self) -> Result<MaEvent> {
+        loop {
+            let result = self.rx_event.recv().await;
+            if let Some(event) = self.filter_result(result) {
+                return event;
+            }
+        }
+    }
+
+    /// Like [`EventStream::recv`] but blocks the thread, must not be called in an async context
+    pub(crate) fn blocking_recv(&mut self) -> Result<MaEvent> {
+        loop {
+            let result = self.rx_event.blocking_recv();
+            if let Some(event) = self.filter_result(result) {
+                return event;
+            }
+        }
+    }
+
+    fn filter_result(
+        &self,
+        result: std::result::Result<MaEvent, RecvError>,
+    ) -> Option<Result<MaEvent>> {
+        match result {
+            Ok(event) if self.filter.matches(&event) => Some(Ok(event)),
+            Ok(_) => None,
+            Err(RecvError::Lagged(missed)) => Some(Ok(MaEvent::Lagged(missed))),
+            Err(RecvError::Closed) => Some(Err(Ma2Error::EventChannelClosed.into())),
+        }
+    }
+
+    /// A new stream of the same client, starting with the next event
+    pub(crate) fn resubscribe(&self, filter: EventFilter) -> Self {
+        Self::new(self.rx_event.resubscribe(), filter)
+    }
+
+    pub fn filter(&self) -> &EventFilter {
+        &self.filter
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{ButtonData, ButtonExecutor, ButtonState, DeskData};
+
+    #[test]
+    fn test_filter() {
+        let button = |exec_id| {
+            MaEvent::ButtonChanged(ButtonData::new(
+                ButtonExecutor::new(1, exec_id),
+                "",
+                "",
+                ButtonState::Pressed,
+            ))
+        };
+        let warning = MaEvent::Warning(Arc::new(Ma2Error::ConnectionLost));
+        let data = MaEvent::DataChanged(DeskData::new());
+
+        let all = EventFilter::new();
+        assert!(all.matches(&button(101)) && all.matches(&warning));
+
+        let buttons = EventFilter::new()
+            .kind(EventKind::Button)
+            .executor(ButtonExecutor::new(1, 101));
+        assert!(buttons.matches(&button(101)));
+        assert!(!buttons.matches(&button(102)));
+        assert!(!buttons.matches(&warning));
+        assert!(buttons.matches(&MaEvent::Lagged(3)));
+
+        let executor = EventFilter::new().executor(ButtonExecutor::new(1, 102));
+        assert!(executor.matches(&button(102)) && executor.matches(&data));
+        assert!(!executor.matches(&button(101)));
+    }
+
+    #[tokio::test]
+    async fn test_lagged() {
+        let (tx_event, rx_event) = broadcast::channel(2);
+        let mut events = EventStream::new(rx_event, EventFilter::new().kind(EventKind::Data));
+        for _ in 0..3 {
+            tx_event
+                .send(MaEvent::DataChanged(DeskData::new()))
+                .unwrap();
+        }
+        assert!(matches!(events.recv().await.unwrap(), MaEvent::Lagged(1)));
+        for _ in 0..2 {
+            assert!(matches!(
+                events.recv().await.unwrap(),
+                MaEvent::DataChanged(_)
+            ));
+        }
+
+        // Filtered events are skipped
+        tx_event.send(MaEvent::Reconnected).unwrap();
+        drop(tx_event);
+        let err = events.recv().await.unwrap_err();
+        assert!(matches!(*err, Ma2Error::EventChannelClosed));
+    }
+}
@@ -1,25 +1,63 @@
+use std::sync::Arc;
+
+use tokio::sync::oneshot;
+
 use crate::{
-    types::{ButtonData, FaderData},
-    types::{ButtonExecutor, FaderExecutor},
-    Ma2Error,
+    client::{ClientStats, PollPriority, PollStats, SubscriptionId},
+    types::{ButtonData, ButtonRange, ButtonState, FaderButton, FaderData, FaderRange},
+    types::{DataKey, DeskData},
+    types::{ButtonExecutor, CueStatus, FaderExecutor},
+    Ma2Error, Result,
 };
 
 /// A request that can be send to the GrandMa2 client in order to trigger an action.
 #[derive(Debug)]
 pub enum MaRequest {
     Disconnect,
-    SubscribeButton(ButtonExecutor, ButtonExecutor),
-    SubscribeFader(FaderExecutor, FaderExecutor),
-    SetButton(ButtonExecutor, ButtonData),
-    SetFader(FaderExecutor, FaderData),
+    SubscribeButton(SubscriptionId, ButtonRange),
+    SubscribeFader(SubscriptionId, FaderRange),
+    Unsubscribe(SubscriptionId),
+    SetPollPriority(SubscriptionId, PollPriority),
+    /// Answer with the stats of all polls
+    PollStats(oneshot::Sender<Vec<PollStats>>),
+    SetButton(ButtonExecutor, ButtonState),
+    SetFader(FaderExecutor, f32),
+    SetFaderButton(FaderExecutor, FaderButton, ButtonState),
+    Command(String),
+    /// Move a fader and answer once the desk reports the new value
+    SetFaderConfirmed(FaderExecutor, f32, oneshot::Sender<Result<FaderData>>),
+    /// Press or release a button and answer once the desk reports the button in that state
+    SetButtonConfirmed(ButtonExecutor, ButtonState, oneshot::Sender<Result<ButtonData>>),
+    /// Answer once the login has been accepted or rejected
+    WaitForLogin(oneshot::Sender<Result<()>>),
+    /// Read the given keys once, the response is sent back through the channel
+    GetData(Vec<DataKey>, oneshot::Sender<Result<DeskData>>),
+    /// Read the given keys on every poll, an empty list stops polling
+    PollData(Vec<DataKey>),
 }
 
 /// A event that can be read by the user to trigger further actions.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum MaEvent {
+    /// The connection to the desk has been lost, the client tries to reconnect
     Disconnected,
+    /// The connection has been reopened, login and subscriptions are restored
+    Reconnected,
     LoginSuccessful(bool),
+    /// The desk reported a different page than the one that has been requested
+    PageChanged(u8),
     FaderChanged(FaderData),
     ButtonChanged(ButtonData),
-    Error(Ma2Error),
+    /// The current or next cue of an executor or the fade progress changed
+    CueChanged(CueStatus),
+    /// Values of a `getdata` response that differ from the last known ones
+    DataChanged(DeskData),
+    /// Periodic connection health, see [`crate::GrandMa2Builder::stats_interval`]
+    Stats(ClientStats),
+    /// The desk sent something the client does not understand, the client keeps running
+    Warning(Arc<Ma2Error>),
+    /// A request failed, the client keeps running
+    Error(Arc<Ma2Error>),
+    /// The receiver fell behind and missed this many events
+    Lagged(u64),
 }
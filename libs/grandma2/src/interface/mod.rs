@@ -1,16 +1,40 @@
+mod builder;
+mod events;
 mod messages;
 
 extern crate md5;
 
+use std::{
+    sync::{Arc, PoisonError, RwLock},
+    time::Duration,
+};
+
 use tokio::{
-    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
-    task::JoinHandle,
+    sync::{
+        broadcast,
+        mpsc::{unbounded_channel, UnboundedSender},
+        oneshot, Notify,
+    },
 };
 
-use crate::{client::GrandMa2Client, types::FaderExecutor, ButtonExecutor, Ma2Error, Result};
+use crate::{
+    client::{
+        connect_websocket, Backoff, ClientConfig, ClientStats, GrandMa2Client, Ma2State,
+        PollPriority, PollStats, RecordEvent, Recorder, SharedState, SubscriptionId,
+    },
+    types::{
+        ButtonData, ButtonRange, ButtonState, CueAction, CueCommand, CueNumber, CueStatus, DataKey,
+        DeskData, FaderButton, FaderData, FaderExecutor, FaderRange, Ma2Data, Playback,
+    },
+    ButtonExecutor, Executor, Ma2Error, Result,
+};
 
+pub use builder::GrandMa2Builder;
+pub use events::{EventFilter, EventKind, EventStream};
 pub use messages::{MaEvent, MaRequest};
 
+use events::DEFAULT_EVENT_CAPACITY;
+
 /// The main GrandMa2 struct to connect and control GrandMa2
 ///
 /// ``` rust,ignore
@@ -33,12 +57,13 @@ pub use messages::{MaEvent, MaRequest};
 /// ```
 #[derive(Debug)]
 pub struct GrandMa2 {
-    url: String,
-    username: String,
-    password: String,
+    config: ClientConfig,
     tx_request: Option<UnboundedSender<MaRequest>>,
-    rx_event: Option<UnboundedReceiver<MaEvent>>,
-    join_handler: Option<JoinHandle<()>>,
+    rx_event: Option<EventStream>,
+    event_capacity: usize,
+    subscription_counter: u64,
+    state: SharedState,
+    shutdown: Arc<Notify>,
 }
 
 impl GrandMa2 {
@@ -48,38 +73,74 @@ impl GrandMa2 {
         password: impl Into<String>,
     ) -> Self {
         Self {
-            url: url.into(),
-            username: username.into(),
-            password: password.into(),
+            config: ClientConfig::new(url.into(), username.into(), password.into()),
             tx_request: None,
             rx_event: None,
-            join_handler: None,
+            event_capacity: DEFAULT_EVENT_CAPACITY,
+            subscription_counter: 0,
+            state: Arc::new(RwLock::new(Ma2State::new())),
+            shutdown: Arc::new(Notify::new()),
         }
     }
 
+    /// Configure a connection with more options than [`GrandMa2::new`]
+    pub fn builder(
+        url: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> GrandMa2Builder {
+        GrandMa2Builder::new(Self::new(url, username, password))
+    }
+
+    /// Configure how the client reconnects after the connection has been lost
+    ///
+    /// `None` disables reconnecting, [`GrandMa2Client::run`] then returns on connection loss.
+    pub fn set_reconnect(&mut self, backoff: Option<Backoff>) {
+        self.config.backoff = backoff;
+    }
+
+    /// How long to wait for the desk to answer, requests fail with [`Ma2Error::Timeout`] after
+    ///
+    /// Like [`GrandMa2::set_reconnect`] this has to be set before connecting.
+    pub fn set_request_timeout(&mut self, timeout: Duration) {
+        self.config.request_timeout = timeout;
+    }
+
+    /// Number of events every [`EventStream`] buffers before it reports [`MaEvent::Lagged`]
+    ///
+    /// Like [`GrandMa2::set_reconnect`] this has to be set before connecting.
+    pub fn set_event_capacity(&mut self, capacity: usize) {
+        self.event_capacity = capacity.max(1);
+    }
+
     pub async fn connect(&mut self) -> Result<GrandMa2Client> {
         // Connect to the websocket
-        let (ws_stream, _response) =
-            tokio_tungstenite::connect_async(&self.url)
-                .await
-                .map_err(|tungstenite_error| Ma2Error::FailedToConnect {
-                    url: self.url.clone(),
-                    tungstenite_error,
-                })?;
+        let ws_stream = connect_websocket(&self.config).await?;
+        let recorder = match &self.config.record {
+            Some(path) => {
+                let mut recorder = Recorder::create(path)?;
+                recorder.record(RecordEvent::Connected(self.config.url.clone()))?;
+                Some(recorder)
+            }
+            None => None,
+        };
 
         // Create channels to communicate between the client thread and the interface
         let (tx_request, rx_request) = unbounded_channel::<MaRequest>();
-        let (tx_event, rx_event) = unbounded_channel::<MaEvent>();
+        let (tx_event, rx_event) = broadcast::channel::<MaEvent>(self.event_capacity);
 
         self.tx_request = Some(tx_request);
-        self.rx_event = Some(rx_event);
+        self.rx_event = Some(EventStream::new(rx_event, EventFilter::new()));
+        self.shutdown = Arc::new(Notify::new());
 
         Ok(GrandMa2Client::new(
             ws_stream,
             rx_request,
             tx_event,
-            self.username.clone(),
-            self.password.clone(),
+            self.shutdown.clone(),
+            self.config.clone(),
+            self.state.clone(),
+            recorder,
         ))
     }
 
@@ -92,31 +153,247 @@ impl GrandMa2 {
         Err(Ma2Error::NotYetConnected.into())
     }
 
+    /// Waits for the next event of the built-in event stream
+    ///
+    /// Other tasks can listen independently with [`GrandMa2::subscribe`].
     pub async fn recv(&mut self) -> Result<MaEvent> {
         if let Some(rx_event) = self.rx_event.as_mut() {
-            return rx_event
-                .recv()
-                .await
-                .ok_or(Ma2Error::EventChannelClosed.into());
+            return rx_event.recv().await;
         }
         Err(Ma2Error::WebsocketNotYetConnected.into())
     }
 
-    pub fn subscribe_fader(&mut self, start: FaderExecutor, end: FaderExecutor) -> Result<()> {
-        let msg = MaRequest::SubscribeFader(start, end);
+    /// A new stream receiving all events from now on
+    pub fn subscribe(&self) -> Result<EventStream> {
+        self.subscribe_filtered(EventFilter::new())
+    }
+
+    /// A new stream receiving the events matching `filter` from now on
+    pub fn subscribe_filtered(&self, filter: EventFilter) -> Result<EventStream> {
+        match &self.rx_event {
+            Some(rx_event) => Ok(rx_event.resubscribe(filter)),
+            None => Err(Ma2Error::NotYetConnected.into()),
+        }
+    }
+
+    /// Watch the faders from `start` to `end`, changes are reported as [`MaEvent::FaderChanged`]
+    ///
+    /// Both faders have to be on the same page. Subscriptions are kept when reconnecting.
+    pub fn subscribe_fader(
+        &mut self,
+        start: FaderExecutor,
+        end: FaderExecutor,
+    ) -> Result<SubscriptionId> {
+        let id = self.next_subscription_id();
+        let msg = MaRequest::SubscribeFader(id, FaderRange::from(start, end)?);
+        self.send(msg)?;
+        Ok(id)
+    }
+
+    /// Watch the buttons from `start` to `end`, changes are reported as [`MaEvent::ButtonChanged`]
+    ///
+    /// Both buttons have to be on the same page. Subscriptions are kept when reconnecting.
+    pub fn subscribe_button(
+        &mut self,
+        start: ButtonExecutor,
+        end: ButtonExecutor,
+    ) -> Result<SubscriptionId> {
+        let id = self.next_subscription_id();
+        let msg = MaRequest::SubscribeButton(id, ButtonRange::from(start, end)?);
+        self.send(msg)?;
+        Ok(id)
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> Result<()> {
+        let msg = MaRequest::Unsubscribe(id);
         self.send(msg)
     }
 
-    pub fn subscribe_button(&mut self, start: ButtonExecutor, end: ButtonExecutor) -> Result<()> {
-        let msg = MaRequest::SubscribeButton(start, end);
+    /// How often the executors of a subscription are polled, see [`PollPriority`]
+    pub fn set_poll_priority(&mut self, id: SubscriptionId, priority: PollPriority) -> Result<()> {
+        let msg = MaRequest::SetPollPriority(id, priority);
         self.send(msg)
     }
 
+    /// How often each poll has been sent and found changes
+    pub async fn poll_stats(&mut self) -> Result<Vec<PollStats>> {
+        let (tx_stats, rx_stats) = oneshot::channel();
+        self.send(MaRequest::PollStats(tx_stats))?;
+        rx_stats
+            .await
+            .map_err(|_| Ma2Error::ConnectionLost.into())
+    }
+
+    fn next_subscription_id(&mut self) -> SubscriptionId {
+        self.subscription_counter += 1;
+        SubscriptionId::new(self.subscription_counter)
+    }
+
+    /// The last known state of a subscribed fader
+    pub fn get_fader(&self, fader: FaderExecutor) -> Option<FaderData> {
+        self.read_state(|state| state.get_fader(fader).cloned())
+    }
+
+    /// The last known state of a subscribed button
+    pub fn get_button(&self, button: ButtonExecutor) -> Option<ButtonData> {
+        self.read_state(|state| state.get_button(button).cloned())
+    }
+
+    /// Message counts, poll latency, reconnects and other connection health
+    pub fn stats(&self) -> ClientStats {
+        self.read_state(|state| state.stats().clone())
+    }
+
+    /// Current and next cue of a subscribed executor, changes are reported as
+    /// [`MaEvent::CueChanged`]
+    pub fn get_cues(&self, executor: impl Into<Executor>) -> Option<CueStatus> {
+        self.read_state(|state| state.get_cues(executor.into()))
+    }
+
+    /// The last known state of all subscribed faders and buttons
+    ///
+    /// Useful to bring a late consumer up to date before it handles change events.
+    pub fn snapshot(&self) -> Ma2Data {
+        self.read_state(Ma2State::snapshot)
+    }
+
+    /// The last known values of all polled or requested [`DataKey`]s
+    pub fn desk_data(&self) -> DeskData {
+        self.read_state(|state| state.get_data().clone())
+    }
+
+    fn read_state<T>(&self, f: impl FnOnce(&Ma2State) -> T) -> T {
+        f(&self.state.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Move a fader to `value` which ranges from `0.0` to `1.0`
+    ///
+    /// Fast moves are coalesced and values the desk just reported are not sent back, see
+    /// [`crate::GrandMa2Builder::fader_interval`].
+    pub fn set_fader(&mut self, fader: FaderExecutor, value: f32) -> Result<()> {
+        let msg = MaRequest::SetFader(fader, value);
+        self.send(msg)
+    }
+
+    pub fn press_button(&mut self, button: ButtonExecutor) -> Result<()> {
+        let msg = MaRequest::SetButton(button, ButtonState::Pressed);
+        self.send(msg)
+    }
+
+    pub fn release_button(&mut self, button: ButtonExecutor) -> Result<()> {
+        let msg = MaRequest::SetButton(button, ButtonState::Released);
+        self.send(msg)
+    }
+
+    pub fn press_fader_button(&mut self, fader: FaderExecutor, button: FaderButton) -> Result<()> {
+        let msg = MaRequest::SetFaderButton(fader, button, ButtonState::Pressed);
+        self.send(msg)
+    }
+
+    pub fn release_fader_button(
+        &mut self,
+        fader: FaderExecutor,
+        button: FaderButton,
+    ) -> Result<()> {
+        let msg = MaRequest::SetFaderButton(fader, button, ButtonState::Released);
+        self.send(msg)
+    }
+
+    /// Execute a command on the command line of the desk
+    ///
+    /// Accepts a plain string like `"Go Executor 1.101"` or a typed [`crate::Command`].
+    pub fn command(&mut self, command: impl ToString) -> Result<()> {
+        let msg = MaRequest::Command(command.to_string());
+        self.send(msg)
+    }
+
+    /// Fade to the next cue of a sequence or of the sequence assigned to an executor
+    pub fn go(&mut self, playback: impl Into<Playback>) -> Result<()> {
+        self.command(CueCommand::new(CueAction::Go, playback))
+    }
+
+    /// Fade back to the previous cue
+    pub fn go_back(&mut self, playback: impl Into<Playback>) -> Result<()> {
+        self.command(CueCommand::new(CueAction::GoBack, playback))
+    }
+
+    /// Fails with [`Ma2Error::InvalidCueNumber`] if `cue` is negative, infinite or NaN
+    pub fn goto_cue(&mut self, playback: impl Into<Playback>, cue: f32) -> Result<()> {
+        let cue = CueNumber::new(cue)?;
+        self.command(CueCommand::new(CueAction::Goto(cue), playback))
+    }
+
+    /// Halt the running fade, the next [`GrandMa2::go`] continues it
+    pub fn pause(&mut self, playback: impl Into<Playback>) -> Result<()> {
+        self.command(CueCommand::new(CueAction::Pause, playback))
+    }
+
+    pub fn off(&mut self, playback: impl Into<Playback>) -> Result<()> {
+        self.command(CueCommand::new(CueAction::Off, playback))
+    }
+
+    /// Read the console state of the given keys
+    pub async fn get_data(&mut self, keys: &[DataKey]) -> Result<DeskData> {
+        let (tx_data, rx_data) = oneshot::channel();
+        self.send(MaRequest::GetData(keys.to_vec(), tx_data))?;
+        Self::response(rx_data).await
+    }
+
+    /// Move a fader and wait until the desk reports the new value
+    pub async fn set_fader_confirmed(
+        &mut self,
+        fader: FaderExecutor,
+        value: f32,
+    ) -> Result<FaderData> {
+        let (tx_data, rx_data) = oneshot::channel();
+        self.send(MaRequest::SetFaderConfirmed(fader, value, tx_data))?;
+        Self::response(rx_data).await
+    }
+
+    /// Press or release a button and wait for the desk to report the button in that state
+    pub async fn set_button_confirmed(
+        &mut self,
+        button: ButtonExecutor,
+        state: ButtonState,
+    ) -> Result<ButtonData> {
+        let (tx_data, rx_data) = oneshot::channel();
+        self.send(MaRequest::SetButtonConfirmed(button, state, tx_data))?;
+        Self::response(rx_data).await
+    }
+
+    /// Wait until the desk accepted the login, fails with [`Ma2Error::LoginFailed`] otherwise
+    pub async fn wait_for_login(&mut self) -> Result<()> {
+        let (tx_login, rx_login) = oneshot::channel();
+        self.send(MaRequest::WaitForLogin(tx_login))?;
+        Self::response(rx_login).await
+    }
+
+    /// Waits for the client to answer a request
+    ///
+    /// Fails with [`Ma2Error::Timeout`] if the desk did not answer in time and with
+    /// [`Ma2Error::ConnectionLost`] if the connection has been lost before.
+    async fn response<T>(rx_response: oneshot::Receiver<Result<T>>) -> Result<T> {
+        rx_response
+            .await
+            .map_err(|_| Box::new(Ma2Error::ConnectionLost))?
+    }
+
+    /// Poll the console state of the given keys, changes are reported as [`MaEvent::DataChanged`]
+    ///
+    /// Replaces the keys of a previous call, an empty list stops polling.
+    pub fn poll_data(&mut self, keys: &[DataKey]) -> Result<()> {
+        let msg = MaRequest::PollData(keys.to_vec());
+        self.send(msg)
+    }
+
+    /// Stops the client, [`GrandMa2Client::run`] returns once the connection has been closed
+    ///
+    /// Requests sent before are still handled, a client waiting to reconnect stops right away.
     pub fn close_connection(&mut self) {
         // Only close connection if connection has been opened
-        if let Some(tx_request) = self.tx_request.as_mut() {
-            let close_msg = MaRequest::Disconnect;
-            tx_request.send(close_msg).unwrap();
+        if self.tx_request.is_some() {
+            // Stores a permit if the client is busy, so the shutdown cannot be missed
+            self.shutdown.notify_one();
         }
     }
 }
@@ -124,9 +401,6 @@ impl GrandMa2 {
 impl Drop for GrandMa2 {
     fn drop(&mut self) {
         self.close_connection();
-        if let Some(join_handler) = self.join_handler.take() {
-            while !join_handler.is_finished() {}
-        }
     }
 }
 
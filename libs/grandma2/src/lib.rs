@@ -1,9 +1,24 @@
+pub mod blocking;
 mod client;
 pub mod interface;
 mod types;
 
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+
 mod error;
 
+pub use client::{
+    Backoff, ClientStats, Frame, PollPriority, PollStats, Record, RecordEvent, Recording,
+    SubscriptionId,
+};
+#[cfg(feature = "tls")]
+pub use client::TlsConfig;
 pub use error::{Ma2Error, Result};
-pub use interface::GrandMa2;
-pub use types::{Executor, FaderExecutor, ButtonExecutor};
\ No newline at end of file
+pub use interface::{EventFilter, EventKind, EventStream, GrandMa2, GrandMa2Builder};
+pub use types::{
+    BlockButton, BlockFader, ButtonData, ButtonExecutor, ButtonState, Command, CueAction,
+    CueCommand, CueInfo, CueNumber, CueStatus, DataKey, DeskData, Executor, ExecutorBlock,
+    ExecutorChanges, FaderButton, FaderData, FaderExecutor, Ma2Data, Playback, PlaybackInfo,
+    BUTTON_IDS, FADER_IDS,
+};
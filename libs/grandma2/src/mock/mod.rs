@@ -0,0 +1,544 @@
+//! A local grandMA2 web remote server to test clients without a desk
+//!
+//! The server speaks the same websocket dialect as the desk (`Status`, `Session`, `Login`,
+//! `Playbacks`, `playbacks_userInput`, `Close`), keeps an in-memory executor model and lets
+//! tests script faults like rejected logins, expiring sessions or dropped connections.
+//!
+//! ``` rust,ignore
+//! use grandma2::{mock::MockServer, GrandMa2};
+//!
+//! let server = MockServer::start().await?;
+//! let mut grandma = GrandMa2::new(server.url(), "remote", "remote");
+//! let mut client = grandma.connect().await?;
+//! tokio::spawn(async move { client.run().await });
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::broadcast,
+    task::JoinHandle,
+};
+use tokio_tungstenite::{accept_async, WebSocketStream};
+use tungstenite::Message;
+
+use crate::{ButtonExecutor, DataKey, Executor, FaderButton, FaderExecutor};
+
+mod replay;
+
+pub use replay::ReplayServer;
+
+/// Number of responses the real desk sends before it wants the session to be renewed
+pub const DEFAULT_SESSION_LIMIT: u32 = 10;
+
+/// A single executor of the mocked desk
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockExecutor {
+    pub name: String,
+    pub color: String,
+    pub value: f32,
+    pub running: bool,
+    pub buttons: [bool; 3],
+    /// Cue lines as shown on the executor, the first one is the current cue
+    pub cues: Vec<String>,
+    /// Fade progress of the current cue
+    pub progress: Option<f32>,
+}
+
+impl Default for MockExecutor {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            color: "#404040".to_owned(),
+            value: 0.0,
+            running: false,
+            buttons: [false; 3],
+            cues: Vec::new(),
+            progress: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct MockState {
+    username: String,
+    password: String,
+    web_remote_enabled: bool,
+    session_limit: Option<u32>,
+    responding: bool,
+    /// Page that is reported instead of the requested one
+    desk_page: Option<u8>,
+    next_session: i8,
+    executors: BTreeMap<Executor, MockExecutor>,
+    data: BTreeMap<String, String>,
+    received: Vec<Value>,
+    connections: usize,
+}
+
+/// Messages the server pushes to all open connections
+#[derive(Debug, Clone)]
+enum Control {
+    Drop,
+    Close,
+    Send(Message),
+}
+
+/// Local websocket server that behaves like the web remote of a grandMA2
+#[derive(Debug)]
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    tx_control: broadcast::Sender<Control>,
+    join_handle: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts a server on a random local port accepting the login `remote`/`remote`
+    pub async fn start() -> std::io::Result<Self> {
+        Self::with_credentials("remote", "remote").await
+    }
+
+    pub async fn with_credentials(
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState {
+            username: username.into(),
+            password: password.into(),
+            web_remote_enabled: true,
+            session_limit: Some(DEFAULT_SESSION_LIMIT),
+            responding: true,
+            desk_page: None,
+            next_session: 1,
+            executors: BTreeMap::new(),
+            data: BTreeMap::new(),
+            received: Vec::new(),
+            connections: 0,
+        }));
+        let (tx_control, _) = broadcast::channel(16);
+
+        let join_handle = tokio::spawn(Self::accept(listener, state.clone(), tx_control.clone()));
+
+        Ok(Self {
+            address,
+            state,
+            tx_control,
+            join_handle,
+        })
+    }
+
+    /// The url to pass to [`crate::GrandMa2::new`]
+    pub fn url(&self) -> String {
+        format!("ws://{}", self.address)
+    }
+
+    pub fn set_fader(&self, fader: FaderExecutor, value: f32) {
+        self.state()
+            .executors
+            .entry(fader.into())
+            .or_default()
+            .value = value;
+    }
+
+    pub fn set_button(&self, button: ButtonExecutor, running: bool) {
+        self.state()
+            .executors
+            .entry(button.into())
+            .or_default()
+            .running = running;
+    }
+
+    pub fn set_executor(&self, executor: impl Into<Executor>, data: MockExecutor) {
+        self.state().executors.insert(executor.into(), data);
+    }
+
+    pub fn executor(&self, executor: impl Into<Executor>) -> Option<MockExecutor> {
+        self.state().executors.get(&executor.into()).cloned()
+    }
+
+    /// Value reported for `key` in `getdata` responses, unknown keys are reported as `"0"`
+    pub fn set_data(&self, key: DataKey, value: impl Into<String>) {
+        self.state()
+            .data
+            .insert(key.as_str().to_owned(), value.into());
+    }
+
+    /// Every message the server received so far, in order
+    pub fn received(&self) -> Vec<Value> {
+        self.state().received.clone()
+    }
+
+    /// Number of currently open websocket connections
+    pub fn connections(&self) -> usize {
+        self.state().connections
+    }
+
+    /// Answer new sessions with `-1` like a desk with disabled web remote
+    pub fn set_web_remote_enabled(&self, enabled: bool) {
+        self.state().web_remote_enabled = enabled;
+    }
+
+    /// Stop answering after `limit` responses until the session is renewed, `None` never stops
+    pub fn set_session_limit(&self, limit: Option<u32>) {
+        self.state().session_limit = limit;
+    }
+
+    /// Keep the connection open but ignore all requests, like a desk that hangs
+    pub fn set_responding(&self, responding: bool) {
+        self.state().responding = responding;
+    }
+
+    /// Answer playback requests with `page`, like a desk that has been told to show another
+    /// page, `None` answers with the requested page
+    pub fn set_desk_page(&self, page: Option<u8>) {
+        self.state().desk_page = page;
+    }
+
+    /// Closes all open connections without a close handshake, like a rebooting desk
+    pub fn drop_connections(&self) {
+        let _ = self.tx_control.send(Control::Drop);
+    }
+
+    /// Closes all open connections with a close handshake, like a desk shutting down
+    pub fn close_connections(&self) {
+        let _ = self.tx_control.send(Control::Close);
+    }
+
+    /// Sends a raw text frame to all open connections
+    pub fn send_raw(&self, text: impl Into<String>) {
+        self.send_frame(Message::Text(text.into()));
+    }
+
+    /// Sends any websocket frame to all open connections
+    pub fn send_frame(&self, frame: Message) {
+        let _ = self.tx_control.send(Control::Send(frame));
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn accept(
+        listener: TcpListener,
+        state: Arc<Mutex<MockState>>,
+        tx_control: broadcast::Sender<Control>,
+    ) {
+        while let Ok((stream, _)) = listener.accept().await {
+            let connection = MockConnection {
+                state: state.clone(),
+                rx_control: tx_control.subscribe(),
+                session: -1,
+                responses: 0,
+            };
+            tokio::spawn(connection.run(stream));
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.join_handle.abort();
+        self.drop_connections();
+    }
+}
+
+struct MockConnection {
+    state: Arc<Mutex<MockState>>,
+    rx_control: broadcast::Receiver<Control>,
+    session: i8,
+    responses: u32,
+}
+
+impl MockConnection {
+    async fn run(mut self, stream: TcpStream) {
+        let Ok(mut ws) = accept_async(stream).await else {
+            return;
+        };
+        self.state().connections += 1;
+
+        let ready = json!({"status": "server ready", "appType": "gma2"});
+        if ws.send(Message::Text(ready.to_string())).await.is_ok() {
+            self.serve(&mut ws).await;
+        }
+
+        self.state().connections -= 1;
+    }
+
+    async fn serve(&mut self, ws: &mut WebSocketStream<TcpStream>) {
+        loop {
+            tokio::select! {
+                msg = ws.next() => {
+                    let text = match msg {
+                        Some(Ok(Message::Text(text))) => text,
+                        // Sends the queued reply to complete the close handshake
+                        Some(Ok(Message::Close(_))) => {
+                            let _ = ws.flush().await;
+                            return;
+                        }
+                        Some(Err(_)) | None => return,
+                        Some(Ok(_)) => continue,
+                    };
+                    let Ok(request) = serde_json::from_str::<Value>(&text) else {
+                        continue;
+                    };
+                    self.state().received.push(request.clone());
+
+                    let close = request["requestType"] == "close";
+                    if let Some(response) = self.respond(&request) {
+                        if ws.send(Message::Text(response.to_string())).await.is_err() {
+                            return;
+                        }
+                    }
+                    if close {
+                        Self::close(ws).await;
+                        return;
+                    }
+                }
+                control = self.rx_control.recv() => {
+                    match control {
+                        Ok(Control::Send(frame)) => {
+                            if ws.send(frame).await.is_err() {
+                                return;
+                            }
+                        }
+                        Ok(Control::Close) => {
+                            Self::close(ws).await;
+                            return;
+                        }
+                        Ok(Control::Drop) | Err(_) => return,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends a close frame and waits for the client to answer it
+    async fn close(ws: &mut WebSocketStream<TcpStream>) {
+        if ws.close(None).await.is_err() {
+            return;
+        }
+        while let Some(Ok(msg)) = ws.next().await {
+            if let Message::Close(_) = msg {
+                return;
+            }
+        }
+    }
+
+    fn respond(&mut self, request: &Value) -> Option<Value> {
+        if !self.state().responding {
+            return None;
+        }
+        let Some(request_type) = request["requestType"].as_str() else {
+            return self.respond_session(request["session"].as_i64()?);
+        };
+
+        // The real desk stops answering if the session is not renewed regularly
+        let session_limit = self.state().session_limit;
+        if session_limit.is_some_and(|limit| self.responses >= limit) {
+            return None;
+        }
+
+        let response = match request_type {
+            "login" => {
+                let state = self.state();
+                let hashed_password = format!("{:x}", md5::compute(&state.password));
+                let result = request["username"] == state.username.as_str()
+                    && request["password"] == hashed_password.as_str();
+                Some(json!({
+                    "realtime": false,
+                    "responseType": "login",
+                    "result": result,
+                    "worldIndex": 0,
+                }))
+            }
+            "playbacks" => Some(self.respond_playbacks(request)),
+            "getdata" => Some(self.respond_get_data(request)),
+            "playbacks_userInput" => {
+                self.handle_user_input(request);
+                None
+            }
+            _ => None,
+        };
+        if response.is_some() {
+            self.responses += 1;
+        }
+        response
+    }
+
+    fn respond_session(&mut self, session: i64) -> Option<Value> {
+        self.responses = 0;
+        let state = self.state.clone();
+        let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+        if !state.web_remote_enabled {
+            return Some(json!({"realtime": false, "session": -1, "worldIndex": 0}));
+        }
+        if session <= 0 {
+            // New session, the desk wants a login
+            self.session = state.next_session;
+            state.next_session = state.next_session.wrapping_add(1).max(1);
+            return Some(json!({
+                "realtime": false,
+                "session": self.session,
+                "forceLogin": true,
+                "worldIndex": 0,
+            }));
+        }
+        Some(json!({"realtime": false, "session": self.session, "worldIndex": 0}))
+    }
+
+    fn respond_playbacks(&self, request: &Value) -> Value {
+        let state = self.state();
+        let page_index = request["pageIndex"].as_u64().unwrap_or(0);
+        // Pages beyond the last one are answered without any items
+        let page = state.desk_page.or_else(|| {
+            u8::try_from(page_index)
+                .ok()
+                .and_then(|index| index.checked_add(1))
+        });
+        let empty = Vec::new();
+        let start_indices = request["startIndex"].as_array().unwrap_or(&empty);
+        let items_counts = request["itemsCount"].as_array().unwrap_or(&empty);
+        let items_types = request["itemsType"].as_array().unwrap_or(&empty);
+
+        let mut item_groups = Vec::new();
+        let groups = start_indices.iter().zip(items_counts).zip(items_types);
+        for ((start, count), items_type) in groups.filter(|_| page.is_some()) {
+            let start = start.as_u64().unwrap_or(0) as u16;
+            let count = count.as_u64().unwrap_or(0) as u16;
+            let items_type = items_type.as_u64().unwrap_or(0);
+            let offset = if items_type == 3 { 100 } else { 0 };
+
+            // Indices below the offset or beyond the last executor are skipped
+            let items: Vec<Value> = (start..start.saturating_add(count))
+                .filter_map(|index| {
+                    let exec_index = index.checked_sub(offset)?;
+                    let executor = Executor::new(page?, index.checked_add(1)?);
+                    let data = state.executors.get(&executor).cloned().unwrap_or_default();
+                    Some(playback_item(&data, exec_index, items_type == 2))
+                })
+                .collect();
+            let rows: Vec<Value> = items
+                .chunks(5)
+                .map(|row| Value::from(row.to_vec()))
+                .collect();
+
+            let mut group = json!({"itemsType": items_type, "cntPages": 10000, "items": rows});
+            if offset > 0 {
+                group["iExecOff"] = json!(offset);
+            }
+            item_groups.push(group);
+        }
+
+        json!({
+            "realtime": false,
+            "responseType": "playbacks",
+            "responseSubType": 2,
+            "iPage": page.map_or(page_index.saturating_add(1), u64::from),
+            "itemGroups": item_groups,
+            "worldIndex": 0,
+        })
+    }
+
+    fn respond_get_data(&self, request: &Value) -> Value {
+        let state = self.state();
+        let data: Vec<Value> = request["data"]
+            .as_str()
+            .unwrap_or_default()
+            .split(',')
+            .filter(|key| !key.is_empty())
+            .map(|key| {
+                let value = state.data.get(key).map_or("0", String::as_str);
+                json!({ key: value })
+            })
+            .collect();
+
+        json!({
+            "realtime": false,
+            "responseType": "getdata",
+            "data": data,
+            "worldIndex": 0,
+        })
+    }
+
+    fn handle_user_input(&self, request: &Value) {
+        let page = request["pageIndex"].as_u64().unwrap_or(0) as u8 + 1;
+        let id = request["execIndex"].as_u64().unwrap_or(0) as u16 + 1;
+        let mut state = self.state();
+        let executor = state.executors.entry(Executor::new(page, id)).or_default();
+
+        if let Some(value) = request["faderValue"].as_f64() {
+            executor.value = value as f32;
+        } else if let Some(pressed) = request["pressed"].as_bool() {
+            match request["buttonId"].as_u64().unwrap_or(0) {
+                // Button executors only have a single button that starts the executor
+                _ if id > 100 => executor.running = pressed,
+                button_id => {
+                    if let Some(button) = executor.buttons.get_mut(button_id as usize) {
+                        *button = pressed;
+                    }
+                }
+            }
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The cue area of a playback item, empty if the executor has no cues
+fn cues(data: &MockExecutor) -> Value {
+    if data.cues.is_empty() {
+        return json!({});
+    }
+    let items: Vec<Value> = data
+        .cues
+        .iter()
+        .enumerate()
+        .map(|(index, text)| match data.progress {
+            Some(progress) if index == 0 => {
+                json!({"t": text, "c": "#FFFFFF", "pgs": {"v": progress}})
+            }
+            _ => json!({"t": text, "c": "#FFFFFF", "pgs": {}}),
+        })
+        .collect();
+    json!({"bC": "#3F3F3F", "items": items})
+}
+
+/// A single item of a playbacks response in the format of the desk
+fn playback_item(data: &MockExecutor, exec_index: u16, with_blocks: bool) -> Value {
+    let mut item = json!({
+        "i": {"t": (exec_index + 1).to_string(), "c": "#C0C0C0"},
+        "oType": {"t": ""},
+        "oI": {"t": ""},
+        "tt": {"t": data.name, "c": "#FFFFFF"},
+        "bC": "#000000",
+        "bdC": data.color,
+        "cues": cues(data),
+        "combinedItems": 1,
+        "iExec": exec_index,
+        "isRun": u8::from(data.running),
+    });
+    if with_blocks {
+        let button = |id: FaderButton| json!({"id": id.id(), "t": "", "s": data.buttons[id.id() as usize], "c": "#FFFFFF"});
+        item["executorBlocks"] = json!([{
+            "button1": button(FaderButton::Button1),
+            "button2": button(FaderButton::Button2),
+            "button3": button(FaderButton::Button3),
+            "fader": {"v": data.value, "min": 0.0, "max": 1.0},
+        }]);
+    }
+    item
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,151 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use tokio_tungstenite::{accept_async, WebSocketStream};
+use tungstenite::Message;
+
+use crate::{Frame, RecordEvent, Recording};
+
+/// Plays the desk side of a [`Recording`] back to a client
+///
+/// Every connection replays the next connection of the recording. Each frame of the desk is
+/// sent once the client has sent as many messages as before this frame in the recording, so
+/// a session is reproduced in the same order regardless of timing.
+///
+/// ``` rust,ignore
+/// let server = ReplayServer::start(Recording::from_file("desk.jsonl")?).await?;
+/// let mut grandma = GrandMa2::new(server.url(), "remote", "remote");
+/// ```
+#[derive(Debug)]
+pub struct ReplayServer {
+    address: SocketAddr,
+    received: Arc<Mutex<Vec<Value>>>,
+    join_handle: JoinHandle<()>,
+}
+
+impl ReplayServer {
+    pub async fn start(recording: Recording) -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let join_handle = tokio::spawn(Self::accept(
+            listener,
+            Self::connections(&recording),
+            received.clone(),
+        ));
+
+        Ok(Self {
+            address,
+            received,
+            join_handle,
+        })
+    }
+
+    /// The url to pass to [`crate::GrandMa2::new`]
+    pub fn url(&self) -> String {
+        format!("ws://{}", self.address)
+    }
+
+    /// Every text message the client sent so far, in order
+    pub fn received(&self) -> Vec<Value> {
+        self.received
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Splits the recording at every new connection
+    fn connections(recording: &Recording) -> Vec<Vec<RecordEvent>> {
+        let mut connections = vec![Vec::new()];
+        for record in recording.records() {
+            match &record.event {
+                RecordEvent::Connected(_) => connections.push(Vec::new()),
+                event => connections.last_mut().unwrap().push(event.clone()),
+            }
+        }
+        connections.retain(|events| !events.is_empty());
+        connections
+    }
+
+    async fn accept(
+        listener: TcpListener,
+        connections: Vec<Vec<RecordEvent>>,
+        received: Arc<Mutex<Vec<Value>>>,
+    ) {
+        let mut connections = connections.into_iter();
+        while let Ok((stream, _)) = listener.accept().await {
+            let events = connections.next().unwrap_or_default();
+            tokio::spawn(Self::replay(stream, events, received.clone()));
+        }
+    }
+
+    async fn replay(stream: TcpStream, events: Vec<RecordEvent>, received: Arc<Mutex<Vec<Value>>>) {
+        let Ok(mut ws) = accept_async(stream).await else {
+            return;
+        };
+        // Connections beyond the recording are closed right away
+        if events.is_empty() {
+            let _ = ws.close(None).await;
+        }
+
+        for event in events {
+            let done = match event {
+                RecordEvent::Sent(Frame::Text(_)) => !Self::expect_text(&mut ws, &received).await,
+                RecordEvent::Received(Frame::Close) => {
+                    let _ = ws.close(None).await;
+                    true
+                }
+                RecordEvent::Received(frame) => ws.send(frame.into_message()).await.is_err(),
+                _ => false,
+            };
+            if done {
+                break;
+            }
+        }
+
+        // Keep the connection open until the client closes it
+        while Self::expect_text(&mut ws, &received).await {}
+    }
+
+    /// Waits for the next text message of the client, `false` once the connection is closed
+    async fn expect_text(
+        ws: &mut WebSocketStream<TcpStream>,
+        received: &Mutex<Vec<Value>>,
+    ) -> bool {
+        loop {
+            match ws.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    if let Ok(request) = serde_json::from_str(&text) {
+                        received
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .push(request);
+                    }
+                    return true;
+                }
+                // Sends the queued reply to complete the close handshake
+                Some(Ok(Message::Close(_))) => {
+                    let _ = ws.flush().await;
+                    return false;
+                }
+                Some(Ok(_)) => continue,
+                Some(Err(_)) | None => return false,
+            }
+        }
+    }
+}
+
+impl Drop for ReplayServer {
+    fn drop(&mut self) {
+        self.join_handle.abort();
+    }
+}
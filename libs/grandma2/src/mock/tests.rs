@@ -0,0 +1,802 @@
+use std::time::Duration;
+
+use tokio::time::timeout;
+
+use super::*;
+use crate::{
+    interface::MaEvent, Backoff, ButtonState, DataKey, EventFilter, EventKind, Frame, GrandMa2,
+    Ma2Error, Playback, PollPriority, RecordEvent, Recording,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn connect(server: &MockServer, password: &str) -> (GrandMa2, JoinHandle<crate::Result<()>>) {
+    let mut grandma = GrandMa2::new(server.url(), "remote", password);
+    grandma.set_reconnect(Some(Backoff::new(
+        Duration::from_millis(10),
+        Duration::from_millis(100),
+    )));
+    let mut client = grandma.connect().await.unwrap();
+    let handle = tokio::spawn(async move { client.run().await });
+    (grandma, handle)
+}
+
+/// Waits for the first event matching `filter`
+async fn wait_for<T>(grandma: &mut GrandMa2, mut filter: impl FnMut(MaEvent) -> Option<T>) -> T {
+    timeout(TIMEOUT, async {
+        loop {
+            if let Some(value) = filter(grandma.recv().await.unwrap()) {
+                return value;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for event")
+}
+
+/// Waits until the desk has set `fader` to `value`
+async fn wait_for_fader(server: &MockServer, fader: FaderExecutor, value: f32) {
+    timeout(TIMEOUT, async {
+        while !server
+            .executor(fader)
+            .is_some_and(|data| (data.value - value).abs() < 0.001)
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for fader")
+}
+
+#[tokio::test]
+async fn test_login_and_updates() {
+    let server = MockServer::start().await.unwrap();
+    server.set_fader(FaderExecutor::new(1, 2), 0.5);
+    server.set_button(ButtonExecutor::new(1, 102), true);
+
+    let (mut grandma, _handle) = connect(&server, "remote").await;
+    grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+    grandma
+        .subscribe_button(ButtonExecutor::new(1, 101), ButtonExecutor::new(1, 105))
+        .unwrap();
+
+    let login = wait_for(&mut grandma, |event| match event {
+        MaEvent::LoginSuccessful(result) => Some(result),
+        _ => None,
+    })
+    .await;
+    assert!(login);
+    let data = wait_for(&mut grandma, |event| match event {
+        MaEvent::FaderChanged(data) if data.get_executer() == &FaderExecutor::new(1, 2) => {
+            Some(data)
+        }
+        _ => None,
+    })
+    .await;
+    assert_eq!(data.value(), 0.5);
+    let data = wait_for(&mut grandma, |event| match event {
+        MaEvent::ButtonChanged(data) if data.get_executer() == &ButtonExecutor::new(1, 102) => {
+            Some(data)
+        }
+        _ => None,
+    })
+    .await;
+    assert_eq!(data.state(), ButtonState::Pressed);
+    assert!(data.info().running);
+
+    server.set_fader(FaderExecutor::new(1, 2), 0.25);
+    let data = wait_for(&mut grandma, |event| match event {
+        MaEvent::FaderChanged(data) => Some(data),
+        _ => None,
+    })
+    .await;
+    assert_eq!(data.value(), 0.25);
+}
+
+#[tokio::test]
+async fn test_login_failed() {
+    let server = MockServer::start().await.unwrap();
+    let (mut grandma, handle) = connect(&server, "wrong").await;
+
+    let login = wait_for(&mut grandma, |event| match event {
+        MaEvent::LoginSuccessful(result) => Some(result),
+        _ => None,
+    })
+    .await;
+    assert!(!login);
+    let result = timeout(TIMEOUT, handle).await.unwrap().unwrap();
+    assert!(matches!(
+        result.map_err(|err| *err),
+        Err(Ma2Error::LoginFailed { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_session_renewal() {
+    let server = MockServer::start().await.unwrap();
+    let (mut grandma, _handle) = connect(&server, "remote").await;
+    grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+
+    // Far more polls than the session limit of the desk allows without renewal
+    for value in 1..=5 {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let expected = value as f32 / 10.0;
+        server.set_fader(FaderExecutor::new(1, 2), expected);
+        wait_for(&mut grandma, |event| match event {
+            MaEvent::FaderChanged(data) if data.value() == expected => Some(()),
+            _ => None,
+        })
+        .await;
+    }
+}
+
+#[tokio::test]
+async fn test_user_input() {
+    let server = MockServer::start().await.unwrap();
+    let (mut grandma, _handle) = connect(&server, "remote").await;
+    let login = wait_for(&mut grandma, |event| match event {
+        MaEvent::LoginSuccessful(result) => Some(result),
+        _ => None,
+    })
+    .await;
+    assert!(login);
+
+    grandma.set_fader(FaderExecutor::new(1, 3), 0.75).unwrap();
+    grandma.press_button(ButtonExecutor::new(2, 110)).unwrap();
+    grandma
+        .press_fader_button(FaderExecutor::new(1, 3), FaderButton::Button2)
+        .unwrap();
+
+    timeout(TIMEOUT, async {
+        loop {
+            let fader = server
+                .executor(FaderExecutor::new(1, 3))
+                .unwrap_or_default();
+            let button = server
+                .executor(ButtonExecutor::new(2, 110))
+                .unwrap_or_default();
+            if fader.value == 0.75 && fader.buttons == [false, true, false] && button.running {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("desk has not received the user input");
+}
+
+#[tokio::test]
+async fn test_reconnect() {
+    let server = MockServer::start().await.unwrap();
+    server.set_fader(FaderExecutor::new(1, 2), 0.5);
+    let (mut grandma, _handle) = connect(&server, "remote").await;
+    grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::FaderChanged(data) => Some(data),
+        _ => None,
+    })
+    .await;
+
+    server.drop_connections();
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::Disconnected => Some(()),
+        _ => None,
+    })
+    .await;
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::Reconnected => Some(()),
+        _ => None,
+    })
+    .await;
+    let login = wait_for(&mut grandma, |event| match event {
+        MaEvent::LoginSuccessful(result) => Some(result),
+        _ => None,
+    })
+    .await;
+    assert!(login);
+
+    // The state has been cleared, so all subscribed executors are reported again
+    let data = wait_for(&mut grandma, |event| match event {
+        MaEvent::FaderChanged(data) if data.get_executer() == &FaderExecutor::new(1, 2) => {
+            Some(data)
+        }
+        _ => None,
+    })
+    .await;
+    assert_eq!(data.value(), 0.5);
+    assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn test_get_data() {
+    let server = MockServer::start().await.unwrap();
+    server.set_data(DataKey::Clear, "1");
+    let (mut grandma, _handle) = connect(&server, "remote").await;
+
+    let data = timeout(TIMEOUT, grandma.get_data(&[DataKey::Clear, DataKey::High]))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.flag(DataKey::Clear), Some(true));
+    assert_eq!(data.flag(DataKey::High), Some(false));
+    assert_eq!(data.get(DataKey::Solo), None);
+
+    grandma.poll_data(&[DataKey::High]).unwrap();
+    server.set_data(DataKey::High, "1");
+    let changes = wait_for(&mut grandma, |event| match event {
+        MaEvent::DataChanged(changes) if changes.flag(DataKey::High) == Some(true) => Some(changes),
+        _ => None,
+    })
+    .await;
+    assert_eq!(changes.get(DataKey::Clear), None);
+    assert_eq!(grandma.desk_data().flag(DataKey::High), Some(true));
+}
+
+#[tokio::test]
+async fn test_confirmed_requests() {
+    let server = MockServer::start().await.unwrap();
+    let (mut grandma, _handle) = connect(&server, "remote").await;
+    timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let data = grandma
+        .set_fader_confirmed(FaderExecutor::new(1, 4), 0.6)
+        .await
+        .unwrap();
+    assert_eq!(data.get_executer(), &FaderExecutor::new(1, 4));
+    assert!((data.value() - 0.6).abs() < 0.001);
+
+    let data = grandma
+        .set_button_confirmed(ButtonExecutor::new(1, 103), ButtonState::Pressed)
+        .await
+        .unwrap();
+    assert_eq!(data.state(), ButtonState::Pressed);
+}
+
+#[tokio::test]
+async fn test_timeout() {
+    let server = MockServer::start().await.unwrap();
+    let mut grandma = GrandMa2::new(server.url(), "remote", "remote");
+    grandma.set_request_timeout(Duration::from_millis(300));
+    let mut client = grandma.connect().await.unwrap();
+    tokio::spawn(async move { client.run().await });
+    timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap();
+
+    server.set_responding(false);
+    let err = timeout(Duration::from_secs(1), grandma.get_data(&[DataKey::Set]))
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(*err, Ma2Error::Timeout { .. }));
+}
+
+#[tokio::test]
+async fn test_wait_for_login_failed() {
+    let server = MockServer::start().await.unwrap();
+    let (mut grandma, _handle) = connect(&server, "wrong").await;
+
+    let err = timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(*err, Ma2Error::LoginFailed { .. }));
+}
+
+#[tokio::test]
+async fn test_unexpected_messages() {
+    let server = MockServer::start().await.unwrap();
+    let (mut grandma, handle) = connect(&server, "remote").await;
+    timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap();
+
+    server.send_raw("{ not json");
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::Warning(err) if matches!(*err, Ma2Error::CouldNotDeserializeReceiveMsg(..)) => {
+            Some(())
+        }
+        _ => None,
+    })
+    .await;
+    server.send_raw(r#"{"responseType":"unknown","worldIndex":0}"#);
+    server.send_raw(r#"{"text":"Error #9"}"#);
+    let text = wait_for(&mut grandma, |event| match event {
+        MaEvent::Warning(err) => match &*err {
+            Ma2Error::DeskMessage(text) => Some(text.clone()),
+            _ => None,
+        },
+        _ => None,
+    })
+    .await;
+    assert_eq!(text, "Error #9");
+    server.send_frame(Message::Binary(vec![1, 2, 3]));
+    server.send_frame(Message::Ping(vec![4]));
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::Warning(err) if matches!(*err, Ma2Error::UnexpectedFrame(_)) => Some(()),
+        _ => None,
+    })
+    .await;
+
+    // The client is still running and answers requests
+    server.set_data(DataKey::Set, "1");
+    let data = timeout(TIMEOUT, grandma.get_data(&[DataKey::Set]))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.flag(DataKey::Set), Some(true));
+    assert!(!handle.is_finished());
+}
+
+#[tokio::test]
+async fn test_close_handshake() {
+    let server = MockServer::start().await.unwrap();
+    let (mut grandma, handle) = connect(&server, "remote").await;
+    timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap();
+
+    // A desk closing the connection is handled like a lost connection
+    server.close_connections();
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::Reconnected => Some(()),
+        _ => None,
+    })
+    .await;
+    timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap();
+
+    grandma.close_connection();
+    let result = timeout(TIMEOUT, handle).await.unwrap().unwrap();
+    assert!(result.is_ok());
+    let received = server.received();
+    assert_eq!(received.last().unwrap()["requestType"], "close");
+}
+
+#[tokio::test]
+async fn test_event_subscribers() {
+    let server = MockServer::start().await.unwrap();
+    server.set_fader(FaderExecutor::new(1, 3), 0.25);
+    let (mut grandma, _handle) = connect(&server, "remote").await;
+    let mut all = grandma.subscribe().unwrap();
+    let mut faders = grandma
+        .subscribe_filtered(
+            EventFilter::new()
+                .kind(EventKind::Fader)
+                .executor(FaderExecutor::new(1, 3)),
+        )
+        .unwrap();
+    grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+
+    let event = timeout(TIMEOUT, faders.recv()).await.unwrap().unwrap();
+    let MaEvent::FaderChanged(data) = event else {
+        panic!("unexpected event {event:?}");
+    };
+    assert_eq!(data.get_executer(), &FaderExecutor::new(1, 3));
+
+    // Independent streams all see the same events
+    let login = timeout(TIMEOUT, all.recv()).await.unwrap().unwrap();
+    assert!(matches!(login, MaEvent::LoginSuccessful(true)));
+    let login = wait_for(&mut grandma, |event| match event {
+        MaEvent::LoginSuccessful(result) => Some(result),
+        _ => None,
+    })
+    .await;
+    assert!(login);
+}
+
+#[tokio::test]
+async fn test_builder_options() {
+    let server = MockServer::start().await.unwrap();
+    let mut grandma = GrandMa2::builder(server.url(), "remote", "remote")
+        .poll_interval(Duration::from_millis(20))
+        .session_renewal(4)
+        .max_requests(3)
+        .build();
+    let mut client = grandma.connect().await.unwrap();
+    tokio::spawn(async move { client.run().await });
+    grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+    timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap();
+
+    // A shorter session than the desk requires keeps working
+    server.set_session_limit(Some(5));
+    server.set_fader(FaderExecutor::new(1, 5), 0.4);
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::FaderChanged(data) if (data.value() - 0.4).abs() < 0.001 => Some(()),
+        _ => None,
+    })
+    .await;
+    let polls: Vec<_> = server
+        .received()
+        .into_iter()
+        .filter(|request| request["requestType"] == "playbacks")
+        .collect();
+    assert!(polls.iter().all(|poll| poll["maxRequests"] == 3));
+}
+
+#[tokio::test]
+async fn test_connect_timeout() {
+    // Accepts TCP connections but never answers the websocket handshake
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let mut grandma = GrandMa2::builder(url, "remote", "remote")
+        .connect_timeout(Duration::from_millis(100))
+        .build();
+
+    let err = timeout(TIMEOUT, grandma.connect())
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(*err, Ma2Error::Timeout { .. }));
+}
+
+#[tokio::test]
+async fn test_adaptive_polling() {
+    let server = MockServer::start().await.unwrap();
+    let mut grandma = GrandMa2::builder(server.url(), "remote", "remote")
+        .poll_interval(Duration::from_millis(20))
+        .idle_poll_interval(Duration::from_millis(160))
+        .build();
+    let mut client = grandma.connect().await.unwrap();
+    tokio::spawn(async move { client.run().await });
+    let faders = grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+    grandma
+        .subscribe_button(ButtonExecutor::new(2, 101), ButtonExecutor::new(2, 105))
+        .unwrap();
+    grandma
+        .set_poll_priority(faders, PollPriority::High)
+        .unwrap();
+    timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap();
+
+    server.set_fader(FaderExecutor::new(1, 2), 0.3);
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::FaderChanged(data) if (data.value() - 0.3).abs() < 0.001 => Some(()),
+        _ => None,
+    })
+    .await;
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let stats = grandma.poll_stats().await.unwrap();
+    let high = stats.iter().find(|stats| stats.page == 1).unwrap();
+    let idle = stats.iter().find(|stats| stats.page == 2).unwrap();
+    assert_eq!(high.priority, PollPriority::High);
+    assert!(high.changes >= 1);
+    // The unchanged buttons have backed off to the idle interval
+    assert_eq!(idle.interval, Duration::from_millis(160));
+    assert!(idle.polls < high.polls);
+}
+
+#[tokio::test]
+async fn test_record_and_replay() {
+    let path = std::env::temp_dir().join(format!("grandma2-{}.jsonl", std::process::id()));
+    let server = MockServer::start().await.unwrap();
+    server.set_fader(FaderExecutor::new(1, 2), 0.5);
+    let fader_changed = |event| match event {
+        MaEvent::FaderChanged(data) if (data.value() - 0.5).abs() < 0.001 => Some(data),
+        _ => None,
+    };
+
+    let mut grandma = GrandMa2::builder(server.url(), "remote", "remote")
+        .reconnect(None)
+        .record(&path)
+        .build();
+    let mut client = grandma.connect().await.unwrap();
+    let handle = tokio::spawn(async move { client.run().await });
+    grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+    wait_for(&mut grandma, fader_changed).await;
+    grandma.close_connection();
+    timeout(TIMEOUT, handle).await.unwrap().unwrap().unwrap();
+
+    let recording = Recording::from_file(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let records = recording.records();
+    assert_eq!(records[0].event, RecordEvent::Connected(server.url()));
+    assert!(records.windows(2).all(|pair| pair[0].time <= pair[1].time));
+    assert!(records.iter().any(|record| matches!(
+        &record.event,
+        RecordEvent::Sent(Frame::Text(text))
+            if text.contains(r#""requestType":"login""#) && text.contains(r#""password":"redacted""#)
+    )));
+    assert_eq!(recording.received().last(), Some(&Frame::Close));
+
+    // The replayed desk reports the recorded fader without the mock server
+    drop(server);
+    let replay = ReplayServer::start(recording).await.unwrap();
+    let mut grandma = GrandMa2::builder(replay.url(), "remote", "remote")
+        .reconnect(None)
+        .build();
+    let mut client = grandma.connect().await.unwrap();
+    tokio::spawn(async move { client.run().await });
+    grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+    let data = wait_for(&mut grandma, fader_changed).await;
+    assert_eq!(data.get_executer(), &FaderExecutor::new(1, 2));
+    assert!(replay
+        .received()
+        .iter()
+        .any(|request| request["requestType"] == "login"));
+
+    // The recording has a single connection, another one is closed
+    let (mut ws, _) = tokio_tungstenite::connect_async(replay.url())
+        .await
+        .unwrap();
+    let message = timeout(TIMEOUT, futures_util::StreamExt::next(&mut ws)).await;
+    assert!(
+        matches!(message, Ok(Some(Ok(tungstenite::Message::Close(_))))),
+        "{message:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_cues() {
+    let server = MockServer::start().await.unwrap();
+    let executor = FaderExecutor::new(1, 3);
+    server.set_executor(
+        executor,
+        MockExecutor {
+            cues: vec!["1 Intro".to_owned(), "2 Verse".to_owned()],
+            ..Default::default()
+        },
+    );
+    let (mut grandma, _handle) = connect(&server, "remote").await;
+    let mut cues = grandma
+        .subscribe_filtered(EventFilter::new().kind(EventKind::Cue))
+        .unwrap();
+    grandma.subscribe_fader(executor, executor).unwrap();
+
+    let MaEvent::CueChanged(status) = timeout(TIMEOUT, cues.recv()).await.unwrap().unwrap() else {
+        panic!("expected a cue event");
+    };
+    assert_eq!(status.executor, executor.executor());
+    assert_eq!(status.current.unwrap().name(), "Intro");
+    assert_eq!(status.next.unwrap().number(), Some(2.0));
+
+    // A running fade is reported with its progress
+    server.set_executor(
+        executor,
+        MockExecutor {
+            cues: vec!["2 Verse".to_owned(), "3 Chorus".to_owned()],
+            progress: Some(0.4),
+            ..Default::default()
+        },
+    );
+    let MaEvent::CueChanged(status) = timeout(TIMEOUT, cues.recv()).await.unwrap().unwrap() else {
+        panic!("expected a cue event");
+    };
+    assert_eq!(status.progress(), Some(0.4));
+    assert_eq!(grandma.get_cues(executor), Some(status));
+
+    grandma.go(executor).unwrap();
+    grandma.goto_cue(Playback::Sequence(7), 3.0).unwrap();
+    grandma.pause(Playback::Sequence(7)).unwrap();
+    let expected = [
+        "Go Executor 1.3",
+        "Goto Cue 3 Sequence 7",
+        "Pause Sequence 7",
+    ];
+    timeout(TIMEOUT, async {
+        loop {
+            let commands: Vec<_> = server
+                .received()
+                .into_iter()
+                .filter(|request| request["requestType"] == "command")
+                .map(|request| request["command"].as_str().unwrap_or_default().to_owned())
+                .collect();
+            if commands == expected {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_fader_coalescing() {
+    let server = MockServer::start().await.unwrap();
+    let fader = FaderExecutor::new(1, 4);
+    let mut grandma = GrandMa2::builder(server.url(), "remote", "remote")
+        .fader_interval(Duration::from_millis(50))
+        .build();
+    let mut client = grandma.connect().await.unwrap();
+    tokio::spawn(async move { client.run().await });
+    grandma.subscribe_fader(fader, fader).unwrap();
+    timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap();
+
+    for step in 1..=50 {
+        grandma.set_fader(fader, step as f32 / 100.0).unwrap();
+    }
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::FaderChanged(data) if (data.value() - 0.5).abs() < 0.001 => Some(()),
+        _ => None,
+    })
+    .await;
+    let inputs = |server: &MockServer| {
+        server
+            .received()
+            .iter()
+            .filter(|request| request["requestType"] == "playbacks_userInput")
+            .count()
+    };
+    let sent = inputs(&server);
+    assert!(sent < 5, "{sent} inputs have been sent");
+
+    // The value the desk reported is not sent back
+    grandma.set_fader(fader, 0.5).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(inputs(&server), sent);
+}
+
+#[tokio::test]
+async fn test_fader_moved_unsubscribed() {
+    let server = MockServer::start().await.unwrap();
+    let fader = FaderExecutor::new(1, 4);
+    let mut grandma = GrandMa2::builder(server.url(), "remote", "remote")
+        .fader_interval(Duration::from_millis(20))
+        .build();
+    let mut client = grandma.connect().await.unwrap();
+    tokio::spawn(async move { client.run().await });
+    timeout(TIMEOUT, grandma.wait_for_login())
+        .await
+        .unwrap()
+        .unwrap();
+    grandma.set_fader(fader, 0.5).unwrap();
+    wait_for_fader(&server, fader, 0.5).await;
+    // Someone else moves the fader, which is not reported without a subscription
+    server.set_fader(fader, 0.2);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    grandma.set_fader(fader, 0.5).unwrap();
+    wait_for_fader(&server, fader, 0.5).await;
+}
+
+#[tokio::test]
+async fn test_stats() {
+    let server = MockServer::start().await.unwrap();
+    let mut grandma = GrandMa2::builder(server.url(), "remote", "remote")
+        .poll_interval(Duration::from_millis(20))
+        .idle_poll_interval(Duration::from_millis(20))
+        .stats_interval(Duration::from_millis(100))
+        .build();
+    let mut client = grandma.connect().await.unwrap();
+    tokio::spawn(async move { client.run().await });
+    grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+    server.send_raw("not json");
+
+    let stats = wait_for(&mut grandma, |event| match event {
+        MaEvent::Stats(stats) if stats.polls_answered >= 10 => Some(stats),
+        _ => None,
+    })
+    .await;
+    assert_eq!(stats.login_attempts, 1);
+    assert_eq!(stats.deserialize_failures, 1);
+    assert!(stats.session_renewals >= 1);
+    assert!(stats.messages_sent > stats.polls_answered);
+    assert!(stats.messages_received > stats.polls_answered);
+    assert!(stats.poll_latency.is_some());
+    assert!(stats.max_poll_latency >= stats.mean_poll_latency);
+
+    server.drop_connections();
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::Reconnected => Some(()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(grandma.stats().reconnects, 1);
+}
+
+#[tokio::test]
+async fn test_page_changed() {
+    let server = MockServer::start().await.unwrap();
+    server.set_fader(FaderExecutor::new(1, 1), 0.5);
+    server.set_fader(FaderExecutor::new(2, 1), 0.5);
+    let mut grandma = GrandMa2::builder(server.url(), "remote", "remote")
+        .poll_interval(Duration::from_millis(20))
+        .idle_poll_interval(Duration::from_millis(20))
+        .build();
+    let mut client = grandma.connect().await.unwrap();
+    tokio::spawn(async move { client.run().await });
+    let mut pages = grandma
+        .subscribe_filtered(EventFilter::new().kind(EventKind::Page))
+        .unwrap();
+    grandma
+        .subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 5))
+        .unwrap();
+    grandma
+        .subscribe_fader(FaderExecutor::new(2, 1), FaderExecutor::new(2, 5))
+        .unwrap();
+    let mut pending = vec![FaderExecutor::new(1, 1), FaderExecutor::new(2, 1)];
+    wait_for(&mut grandma, |event| match event {
+        MaEvent::FaderChanged(data) => {
+            pending.retain(|fader| fader != data.get_executer());
+            pending.is_empty().then_some(())
+        }
+        _ => None,
+    })
+    .await;
+
+    // Polls alternating between the pages do not change the page of the desk
+    let event = timeout(Duration::from_millis(200), pages.recv()).await;
+    assert!(event.is_err(), "unexpected event {event:?}");
+
+    // The desk switches to another page and back to the watched ones
+    server.set_desk_page(Some(3));
+    let event = timeout(TIMEOUT, pages.recv()).await.unwrap().unwrap();
+    assert!(matches!(event, MaEvent::PageChanged(3)), "{event:?}");
+    server.set_desk_page(None);
+    let page = wait_for(&mut grandma, |event| match event {
+        MaEvent::PageChanged(page) if page != 3 => Some(page),
+        _ => None,
+    })
+    .await;
+    assert!([1, 2].contains(&page), "unexpected page {page}");
+}
+
+#[tokio::test]
+async fn test_mock_out_of_range() {
+    let server = MockServer::start().await.unwrap();
+    let connection = MockConnection {
+        state: server.state.clone(),
+        rx_control: server.tx_control.subscribe(),
+        session: 1,
+        responses: 0,
+    };
+    let items = |response: &Value, group: usize| {
+        response["itemGroups"][group]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row.as_array().unwrap().len())
+            .sum::<usize>()
+    };
+
+    // There is no page after the last one
+    let request = json!({"pageIndex": 255, "startIndex": [0], "itemsCount": [5], "itemsType": [2]});
+    let response = connection.respond_playbacks(&request);
+    assert_eq!(response["itemGroups"], json!([]));
+
+    // Buttons below the offset and executors beyond the last one are skipped
+    let request = json!({
+        "pageIndex": 0,
+        "startIndex": [98, 65530],
+        "itemsCount": [5, 10],
+        "itemsType": [3, 2],
+    });
+    let response = connection.respond_playbacks(&request);
+    assert_eq!(items(&response, 0), 3);
+    assert_eq!(items(&response, 1), 5);
+}
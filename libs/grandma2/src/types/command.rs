@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use super::executor::{ButtonExecutor, Executor, FaderExecutor};
+use crate::{Ma2Error, Result};
+
+/// A grandMA2 command line command
+///
+/// The `Display` implementation renders the command exactly as it would be typed into the
+/// command line of the desk, e.g. `Goto Cue 5 Executor 1.1`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// `Go Executor <executor>`
+    Go(Executor),
+    /// `Goto Cue <cue> Executor <executor>`
+    Goto { cue: CueNumber, executor: Executor },
+    /// `Off Executor <executor>`
+    Off(Executor),
+    /// `Flash Executor <executor>`
+    Flash(Executor),
+    /// `FaderMaster Executor <executor> At <percent>`, `value` ranges from `0.0` to `1.0`
+    Fader { executor: Executor, value: f32 },
+    /// `Store Executor <executor>`
+    Store(Executor),
+    /// `Label Executor <executor> "<name>"`
+    Label { executor: Executor, name: String },
+    /// `Go Macro <macro>`
+    Macro(u16),
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::Go(executor) => write!(f, "Go Executor {executor}"),
+            Command::Goto { cue, executor } => write!(f, "Goto Cue {cue} Executor {executor}"),
+            Command::Off(executor) => write!(f, "Off Executor {executor}"),
+            Command::Flash(executor) => write!(f, "Flash Executor {executor}"),
+            Command::Fader { executor, value } => {
+                let percent = (value.clamp(0.0, 1.0) * 100.0).round();
+                write!(f, "FaderMaster Executor {executor} At {percent}")
+            }
+            Command::Store(executor) => write!(f, "Store Executor {executor}"),
+            Command::Label { executor, name } => {
+                write!(f, "Label Executor {executor} {}", quote(name))
+            }
+            Command::Macro(id) => write!(f, "Go Macro {id}"),
+        }
+    }
+}
+
+/// A cue number like `2.5`, which is neither negative, infinite nor NaN
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct CueNumber(f32);
+
+impl CueNumber {
+    pub fn new(cue: f32) -> Result<Self> {
+        if cue.is_finite() && cue >= 0.0 {
+            Ok(Self(cue))
+        } else {
+            Err(Ma2Error::InvalidCueNumber(cue).into())
+        }
+    }
+
+    pub fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+impl Display for CueNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<f32> for CueNumber {
+    type Error = Box<Ma2Error>;
+
+    fn try_from(value: f32) -> Result<Self> {
+        Self::new(value)
+    }
+}
+
+/// A sequence, either directly or through the executor it is assigned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Playback {
+    Executor(Executor),
+    Sequence(u16),
+}
+
+impl Display for Playback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Playback::Executor(executor) => write!(f, "Executor {executor}"),
+            Playback::Sequence(sequence) => write!(f, "Sequence {sequence}"),
+        }
+    }
+}
+
+impl From<Executor> for Playback {
+    fn from(executor: Executor) -> Self {
+        Playback::Executor(executor)
+    }
+}
+
+impl From<FaderExecutor> for Playback {
+    fn from(fader: FaderExecutor) -> Self {
+        Playback::Executor(fader.into())
+    }
+}
+
+impl From<ButtonExecutor> for Playback {
+    fn from(button: ButtonExecutor) -> Self {
+        Playback::Executor(button.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CueAction {
+    /// Fade to the next cue
+    Go,
+    /// Fade to the previous cue
+    GoBack,
+    /// Fade to the given cue number
+    Goto(CueNumber),
+    /// Halt the running fade, the next `Go` continues it
+    Pause,
+    Off,
+}
+
+/// A cue command for a sequence, e.g. `Goto Cue 5 Sequence 3`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CueCommand {
+    pub action: CueAction,
+    pub playback: Playback,
+}
+
+impl CueCommand {
+    pub fn new(action: CueAction, playback: impl Into<Playback>) -> Self {
+        Self {
+            action,
+            playback: playback.into(),
+        }
+    }
+}
+
+impl Display for CueCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let playback = self.playback;
+        match self.action {
+            CueAction::Go => write!(f, "Go {playback}"),
+            CueAction::GoBack => write!(f, "GoBack {playback}"),
+            CueAction::Goto(cue) => write!(f, "Goto Cue {cue} {playback}"),
+            CueAction::Pause => write!(f, "Pause {playback}"),
+            CueAction::Off => write!(f, "Off {playback}"),
+        }
+    }
+}
+
+/// Put `text` in double quotes so it is read as a single argument
+///
+/// The command line has no escape sequence for quotes, so they are removed from `text`.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', ""))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let executor = Executor::new(1, 101);
+        assert_eq!(Command::Go(executor).to_string(), "Go Executor 1.101");
+        assert_eq!(Command::Off(executor).to_string(), "Off Executor 1.101");
+        assert_eq!(Command::Flash(executor).to_string(), "Flash Executor 1.101");
+        assert_eq!(Command::Store(executor).to_string(), "Store Executor 1.101");
+        assert_eq!(Command::Macro(5).to_string(), "Go Macro 5");
+
+        let executor = Executor::new(2, 1);
+        let goto = |cue| Command::Goto {
+            cue: CueNumber::new(cue).unwrap(),
+            executor,
+        };
+        assert_eq!(goto(5.0).to_string(), "Goto Cue 5 Executor 2.1");
+        assert_eq!(goto(2.5).to_string(), "Goto Cue 2.5 Executor 2.1");
+        assert_eq!(
+            Command::Fader {
+                executor,
+                value: 0.5
+            }
+            .to_string(),
+            "FaderMaster Executor 2.1 At 50"
+        );
+        assert_eq!(
+            Command::Label {
+                executor,
+                name: "Bar \"Chill\" 1".to_owned()
+            }
+            .to_string(),
+            "Label Executor 2.1 \"Bar Chill 1\""
+        );
+    }
+
+    #[test]
+    fn test_cue_command() {
+        let sequence = Playback::Sequence(7);
+        let executor = FaderExecutor::new(1, 3);
+        assert_eq!(
+            CueCommand::new(CueAction::Go, sequence).to_string(),
+            "Go Sequence 7"
+        );
+        assert_eq!(
+            CueCommand::new(CueAction::GoBack, executor).to_string(),
+            "GoBack Executor 1.3"
+        );
+        assert_eq!(
+            CueCommand::new(CueAction::Goto(CueNumber::new(2.5).unwrap()), sequence).to_string(),
+            "Goto Cue 2.5 Sequence 7"
+        );
+        assert_eq!(
+            CueCommand::new(CueAction::Pause, ButtonExecutor::new(2, 101)).to_string(),
+            "Pause Executor 2.101"
+        );
+        assert_eq!(
+            CueCommand::new(CueAction::Off, sequence).to_string(),
+            "Off Sequence 7"
+        );
+    }
+
+    #[test]
+    fn test_invalid_cue_number() {
+        for cue in [f32::NAN, f32::INFINITY, -1.0] {
+            let err = CueNumber::new(cue).unwrap_err();
+            assert!(matches!(*err, Ma2Error::InvalidCueNumber(_)));
+        }
+        assert_eq!(CueNumber::try_from(0.0).unwrap().value(), 0.0);
+    }
+}
@@ -0,0 +1,114 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Console state that can be read with a `getdata` request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DataKey {
+    /// The `Set` key is active
+    Set,
+    /// The programmer has content, `Clear` would clear it
+    Clear,
+    /// Solo mode is active
+    Solo,
+    /// Highlight mode is active
+    High,
+}
+
+impl DataKey {
+    pub const ALL: [DataKey; 4] = [DataKey::Set, DataKey::Clear, DataKey::Solo, DataKey::High];
+
+    /// Name of the key as used by the web remote
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataKey::Set => "set",
+            DataKey::Clear => "clear",
+            DataKey::Solo => "solo",
+            DataKey::High => "high",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        DataKey::ALL.into_iter().find(|key| key.as_str() == name)
+    }
+}
+
+impl Display for DataKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Values of a `getdata` response
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeskData {
+    values: BTreeMap<DataKey, String>,
+}
+
+impl DeskData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Raw value as sent by the desk
+    pub fn get(&self, key: DataKey) -> Option<&str> {
+        self.values.get(&key).map(String::as_str)
+    }
+
+    /// The value read as a flag, the desk sends `"1"` if the state is active
+    pub fn flag(&self, key: DataKey) -> Option<bool> {
+        self.get(key).map(|value| value != "0" && !value.is_empty())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (DataKey, &str)> {
+        self.values
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub(crate) fn insert(&mut self, key: DataKey, value: impl Into<String>) {
+        self.values.insert(key, value.into());
+    }
+
+    /// Takes over all values of `data`, returns the values that changed
+    pub(crate) fn diff_and_update(&mut self, data: &DeskData) -> DeskData {
+        let mut changes = DeskData::new();
+        for (key, value) in data.iter() {
+            if self.get(key) != Some(value) {
+                self.insert(key, value);
+                changes.insert(key, value);
+            }
+        }
+        changes
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let mut state = DeskData::new();
+        let mut data = DeskData::new();
+        data.insert(DataKey::Clear, "1");
+        data.insert(DataKey::High, "0");
+
+        assert_eq!(state.diff_and_update(&data), data);
+        assert_eq!(state.flag(DataKey::Clear), Some(true));
+        assert_eq!(state.flag(DataKey::High), Some(false));
+        assert_eq!(state.flag(DataKey::Solo), None);
+
+        data.insert(DataKey::High, "1");
+        let mut expected = DeskData::new();
+        expected.insert(DataKey::High, "1");
+        assert_eq!(state.diff_and_update(&data), expected);
+        assert!(state.diff_and_update(&data).is_empty());
+    }
+}
@@ -1,5 +1,17 @@
-use std::fmt::Display;
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
 
+use crate::{Ma2Error, Result};
+
+/// Ids of fader executors, the console and all fader wings
+pub const FADER_IDS: RangeInclusive<u16> = 1..=99;
+/// Ids of button executors including extra button rows, `execIndex` of the web remote is a
+/// single byte
+pub const BUTTON_IDS: RangeInclusive<u16> = 101..=256;
+
+/// An executor given by page and id, e.g. `1.101`
+///
+/// `new` does not validate, use [`Executor::try_new`] or parse a string for input from users
+/// or config files.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Executor {
     typ: ExecutorType,
@@ -13,31 +25,51 @@ impl Executor {
             page,
             id,
             typ: match id {
-                0..=99 => ExecutorType::Fader,
-                100..=199 => ExecutorType::Button,
+                _ if page == 0 => ExecutorType::Invalid,
+                id if FADER_IDS.contains(&id) => ExecutorType::Fader,
+                id if BUTTON_IDS.contains(&id) => ExecutorType::Button,
                 _ => ExecutorType::Invalid,
             },
         }
     }
 
+    /// A fader or button executor, pages start at 1
+    ///
+    /// Invalid ids up to 100 are reported as [`Ma2Error::FaderIdOutOfRange`], all others as
+    /// [`Ma2Error::ButtonIdOutOfRange`].
+    pub fn try_new(page: u8, id: u16) -> Result<Self> {
+        let executor = Self::new(page, id);
+        match executor.typ {
+            ExecutorType::Invalid if id <= 100 => Err(Ma2Error::FaderIdOutOfRange(executor).into()),
+            ExecutorType::Invalid => Err(Ma2Error::ButtonIdOutOfRange(executor).into()),
+            _ => Ok(executor),
+        }
+    }
+
     pub fn id(&self) -> u16 {
         self.id
     }
 
+    pub fn page(&self) -> u8 {
+        self.page
+    }
+
+    /// Zero based executor index as used by the web remote (`execIndex`)
+    pub(crate) fn exec_index(&self) -> u8 {
+        self.id.saturating_sub(1) as u8
+    }
+
+    /// Zero based page index as used by the web remote (`pageIndex`)
+    pub(crate) fn page_index(&self) -> u8 {
+        self.page.saturating_sub(1)
+    }
+
     pub fn into_button(self) -> Option<ButtonExecutor> {
-        if self.typ == ExecutorType::Button {
-            return Some(ButtonExecutor(self));
-        }
-        println!("{}", self);
-        None
+        ButtonExecutor::try_from(self).ok()
     }
 
     pub fn into_fader(self) -> Option<FaderExecutor> {
-        if self.typ == ExecutorType::Fader {
-            return Some(FaderExecutor(self));
-        }
-        println!("{}", self);
-        None
+        FaderExecutor::try_from(self).ok()
     }
 }
 
@@ -47,6 +79,27 @@ impl Display for Executor {
     }
 }
 
+/// Parses `page.id`, e.g. `1.101`
+impl FromStr for Executor {
+    type Err = Box<Ma2Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Ma2Error::InvalidExecutor(s.to_owned());
+        let (page, id) = s.trim().split_once('.').ok_or_else(invalid)?;
+        let page = page.parse().map_err(|_| invalid())?;
+        let id = id.parse().map_err(|_| invalid())?;
+        Self::try_new(page, id)
+    }
+}
+
+impl TryFrom<&str> for Executor {
+    type Error = Box<Ma2Error>;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) enum ExecutorType {
     Invalid,
@@ -59,13 +112,74 @@ pub(crate) enum ExecutorType {
 pub struct ButtonExecutor(Executor);
 
 impl ButtonExecutor {
+    /// # Panics
+    ///
+    /// If `page` is 0 or `id` is not in [`BUTTON_IDS`], see [`ButtonExecutor::try_new`]
     pub fn new(page: u8, id: u16) -> Self {
-        Executor::new(page, id).into_button().unwrap()
+        match Self::try_new(page, id) {
+            Ok(button) => button,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_new(page: u8, id: u16) -> Result<Self> {
+        Self::try_from(Executor::new(page, id))
     }
 
     pub fn id(&self) -> u16 {
         self.0.id()
     }
+
+    pub fn page(&self) -> u8 {
+        self.0.page()
+    }
+
+    pub fn executor(&self) -> Executor {
+        self.0
+    }
+}
+
+impl From<ButtonExecutor> for Executor {
+    fn from(value: ButtonExecutor) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Executor> for ButtonExecutor {
+    type Error = Box<Ma2Error>;
+
+    fn try_from(executor: Executor) -> Result<Self> {
+        match executor.typ {
+            ExecutorType::Button => Ok(Self(executor)),
+            _ => Err(Ma2Error::ButtonIdOutOfRange(executor).into()),
+        }
+    }
+}
+
+impl FromStr for ButtonExecutor {
+    type Err = Box<Ma2Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let executor = s.parse::<Executor>().map_err(|err| match *err {
+            Ma2Error::FaderIdOutOfRange(executor) => Ma2Error::ButtonIdOutOfRange(executor).into(),
+            _ => err,
+        })?;
+        Self::try_from(executor)
+    }
+}
+
+impl TryFrom<&str> for ButtonExecutor {
+    type Error = Box<Ma2Error>;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl Display for ButtonExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -73,11 +187,117 @@ impl ButtonExecutor {
 pub struct FaderExecutor(Executor);
 
 impl FaderExecutor {
+    /// # Panics
+    ///
+    /// If `page` is 0 or `id` is not in [`FADER_IDS`], see [`FaderExecutor::try_new`]
     pub fn new(page: u8, id: u16) -> Self {
-        Executor::new(page, id).into_fader().unwrap()
+        match Self::try_new(page, id) {
+            Ok(fader) => fader,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    pub fn try_new(page: u8, id: u16) -> Result<Self> {
+        Self::try_from(Executor::new(page, id))
     }
 
     pub fn id(&self) -> u16 {
         self.0.id()
     }
-}
\ No newline at end of file
+
+    pub fn page(&self) -> u8 {
+        self.0.page()
+    }
+
+    pub fn executor(&self) -> Executor {
+        self.0
+    }
+}
+
+impl From<FaderExecutor> for Executor {
+    fn from(value: FaderExecutor) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Executor> for FaderExecutor {
+    type Error = Box<Ma2Error>;
+
+    fn try_from(executor: Executor) -> Result<Self> {
+        match executor.typ {
+            ExecutorType::Fader => Ok(Self(executor)),
+            _ => Err(Ma2Error::FaderIdOutOfRange(executor).into()),
+        }
+    }
+}
+
+impl FromStr for FaderExecutor {
+    type Err = Box<Ma2Error>;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let executor = s.parse::<Executor>().map_err(|err| match *err {
+            Ma2Error::ButtonIdOutOfRange(executor) => Ma2Error::FaderIdOutOfRange(executor).into(),
+            _ => err,
+        })?;
+        Self::try_from(executor)
+    }
+}
+
+impl TryFrom<&str> for FaderExecutor {
+    type Error = Box<Ma2Error>;
+
+    fn try_from(value: &str) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl Display for FaderExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        assert!(FaderExecutor::try_new(1, 1).is_ok());
+        assert!(FaderExecutor::try_new(2, 99).is_ok());
+        assert!(ButtonExecutor::try_new(1, 101).is_ok());
+        assert!(ButtonExecutor::try_new(1, 256).is_ok());
+
+        let err = FaderExecutor::try_new(0, 1).unwrap_err();
+        assert!(matches!(*err, Ma2Error::FaderIdOutOfRange(_)));
+        let err = FaderExecutor::try_new(1, 0).unwrap_err();
+        assert!(matches!(*err, Ma2Error::FaderIdOutOfRange(_)));
+        let err = ButtonExecutor::try_new(1, 100).unwrap_err();
+        assert!(matches!(*err, Ma2Error::ButtonIdOutOfRange(_)));
+        let err = ButtonExecutor::try_new(1, 5).unwrap_err();
+        assert!(matches!(*err, Ma2Error::ButtonIdOutOfRange(_)));
+        let err = Executor::try_new(1, 300).unwrap_err();
+        assert!(matches!(*err, Ma2Error::ButtonIdOutOfRange(_)));
+        assert_eq!(Executor::new(1, 5).into_button(), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1.101".parse().ok(), Some(ButtonExecutor::new(1, 101)));
+        assert_eq!(
+            FaderExecutor::try_from(" 3.15 ").ok(),
+            Some(FaderExecutor::new(3, 15))
+        );
+        assert_eq!("2.7".parse::<Executor>().ok(), Some(Executor::new(2, 7)));
+        assert_eq!(ButtonExecutor::new(4, 190).to_string(), "4.190");
+
+        let err = "1.101".parse::<FaderExecutor>().unwrap_err();
+        assert!(matches!(*err, Ma2Error::FaderIdOutOfRange(_)));
+        let err = "1.5".parse::<ButtonExecutor>().unwrap_err();
+        assert!(matches!(*err, Ma2Error::ButtonIdOutOfRange(_)));
+        for invalid in ["", "1", "1.", ".1", "a.1", "1.1.1", "256.1"] {
+            let err = invalid.parse::<Executor>().unwrap_err();
+            assert!(matches!(*err, Ma2Error::InvalidExecutor(_)), "{invalid}");
+        }
+    }
+}
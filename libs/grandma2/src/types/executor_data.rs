@@ -1,4 +1,7 @@
-use super::executor::{ButtonExecutor, FaderExecutor};
+use super::{
+    executor::{ButtonExecutor, FaderExecutor},
+    playback::{BlockButton, BlockFader, ExecutorBlock, ExecutorChanges, PlaybackInfo},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ButtonState {
@@ -25,12 +28,32 @@ impl Into<bool> for ButtonState {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// The three buttons that belong to a fader executor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaderButton {
+    Button1,
+    Button2,
+    Button3,
+}
+
+impl FaderButton {
+    /// Button id as used by the web remote (`buttonId`)
+    pub(crate) fn id(&self) -> u16 {
+        match self {
+            FaderButton::Button1 => 0,
+            FaderButton::Button2 => 1,
+            FaderButton::Button3 => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ButtonData {
     executor: ButtonExecutor,
     name: String,
     color: String,
     state: ButtonState,
+    info: PlaybackInfo,
 }
 
 impl ButtonData {
@@ -45,11 +68,44 @@ impl ButtonData {
             name: name.into(),
             color: color.into(),
             state: state.into(),
+            info: PlaybackInfo::default(),
         }
     }
+
+    pub fn with_info(mut self, info: PlaybackInfo) -> Self {
+        self.info = info;
+        self
+    }
+
     pub fn get_executer(&self) -> &ButtonExecutor {
         &self.executor
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Border colour of the executor (`bdC`)
+    pub fn color(&self) -> &str {
+        &self.color
+    }
+
+    pub fn state(&self) -> ButtonState {
+        self.state
+    }
+
+    pub fn info(&self) -> &PlaybackInfo {
+        &self.info
+    }
+
+    /// Compares with the `previous` update of the same executor
+    pub fn changes(&self, previous: &ButtonData) -> ExecutorChanges {
+        let mut changes = info_changes(&self.info, &previous.info);
+        changes.name = self.name != previous.name;
+        changes.color |= self.color != previous.color;
+        changes.buttons = self.state != previous.state;
+        changes
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -57,11 +113,9 @@ pub struct FaderData {
     executor: FaderExecutor,
     name: String,
     color: String,
-    value: f32,
-    touched: bool,
-    button1: ButtonState,
-    button2: ButtonState,
-    button3: ButtonState,
+    info: PlaybackInfo,
+    /// Never empty, the first block is the main fader of the executor
+    blocks: Vec<ExecutorBlock>,
 }
 
 impl FaderData {
@@ -75,20 +129,132 @@ impl FaderData {
         button2: impl Into<ButtonState>,
         button3: impl Into<ButtonState>,
     ) -> Self {
+        let button = |state: ButtonState| BlockButton {
+            state,
+            ..Default::default()
+        };
+        let block = ExecutorBlock {
+            button1: button(button1.into()),
+            button2: button(button2.into()),
+            button3: button(button3.into()),
+            fader: BlockFader {
+                value,
+                touched,
+                max: 1.0,
+                ..Default::default()
+            },
+        };
         Self {
             executor,
             name: name.into(),
             color: color.into(),
-            value,
-            touched,
-            button1: button1.into(),
-            button2: button2.into(),
-            button3: button3.into(),
+            info: PlaybackInfo::default(),
+            blocks: vec![block],
         }
     }
+
+    pub fn with_info(mut self, info: PlaybackInfo) -> Self {
+        self.info = info;
+        self
+    }
+
+    /// Replaces all executor blocks, an empty list keeps the current blocks
+    pub fn with_blocks(mut self, blocks: Vec<ExecutorBlock>) -> Self {
+        if !blocks.is_empty() {
+            self.blocks = blocks;
+        }
+        self
+    }
+
     pub fn get_executer(&self) -> &FaderExecutor {
         &self.executor
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Border colour of the executor (`bdC`)
+    pub fn color(&self) -> &str {
+        &self.color
+    }
+
+    /// Value of the main fader from `0.0` to `1.0`
+    pub fn value(&self) -> f32 {
+        self.blocks[0].fader.value
+    }
+
+    pub fn touched(&self) -> bool {
+        self.blocks[0].fader.touched
+    }
+
+    pub fn button(&self, button: FaderButton) -> ButtonState {
+        let block = &self.blocks[0];
+        match button {
+            FaderButton::Button1 => block.button1.state,
+            FaderButton::Button2 => block.button2.state,
+            FaderButton::Button3 => block.button3.state,
+        }
+    }
+
+    pub fn info(&self) -> &PlaybackInfo {
+        &self.info
+    }
+
+    pub fn blocks(&self) -> &[ExecutorBlock] {
+        &self.blocks
+    }
+
+    /// Compares with the `previous` update of the same executor
+    pub fn changes(&self, previous: &FaderData) -> ExecutorChanges {
+        let mut changes = info_changes(&self.info, &previous.info);
+        changes.name = self.name != previous.name;
+        changes.color |= self.color != previous.color;
+
+        let values = |data: &FaderData| -> Vec<f32> {
+            data.blocks.iter().map(|block| block.fader.value).collect()
+        };
+        let states = |data: &FaderData| -> Vec<ButtonState> {
+            data.blocks
+                .iter()
+                .flat_map(|block| [block.button1.state, block.button2.state, block.button3.state])
+                .collect()
+        };
+        changes.value = values(self) != values(previous);
+        changes.buttons = states(self) != states(previous);
+
+        // Everything that is left after ignoring values and button states
+        let layout = |data: &FaderData| -> Vec<ExecutorBlock> {
+            data.blocks
+                .iter()
+                .map(|block| {
+                    let mut block = block.clone();
+                    block.fader.value = 0.0;
+                    block.fader.value_text = None;
+                    block.button1.state = ButtonState::Released;
+                    block.button2.state = ButtonState::Released;
+                    block.button3.state = ButtonState::Released;
+                    block
+                })
+                .collect()
+        };
+        changes.other |= layout(self) != layout(previous);
+        changes
+    }
+}
+
+/// Changes of the parts every executor type has in common
+fn info_changes(info: &PlaybackInfo, previous: &PlaybackInfo) -> ExecutorChanges {
+    ExecutorChanges {
+        color: info.text_color != previous.text_color
+            || info.background_color != previous.background_color,
+        cues: info.cues != previous.cues || info.cue_color != previous.cue_color,
+        running: info.running != previous.running,
+        other: info.object_type != previous.object_type
+            || info.object_index != previous.object_index
+            || info.combined_items != previous.combined_items,
+        ..Default::default()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -10,7 +10,7 @@ pub struct ButtonRange {
 
 impl ButtonRange {
     pub fn from(button1: ButtonExecutor, button2: ButtonExecutor) -> Result<Self> {
-        if button1 > button2 {
+        if button1 > button2 || button1.page() != button2.page() {
             return Err(Box::new(Ma2Error::InvalidButtonRange(button1, button2)).into());
         }
         let num_buttons = button2.id() - button1.id() + 1;
@@ -19,6 +19,10 @@ impl ButtonRange {
             num_buttons,
         })
     }
+
+    pub fn page(&self) -> u8 {
+        self.start_button.page()
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -29,7 +33,7 @@ pub struct FaderRange {
 
 impl FaderRange {
     pub fn from(fader1: FaderExecutor, fader2: FaderExecutor) -> Result<Self> {
-        if fader1 > fader2 {
+        if fader1 > fader2 || fader1.page() != fader2.page() {
             return Err(Ma2Error::InvalidFaderRange(fader1, fader2).into());
         }
         let num_faders = fader2.id() - fader1.id() + 1;
@@ -38,4 +42,8 @@ impl FaderRange {
             num_faders,
         })
     }
+
+    pub fn page(&self) -> u8 {
+        self.start_fader.page()
+    }
 }
\ No newline at end of file
@@ -1,7 +1,15 @@
+mod command;
+mod desk_data;
 mod executor;
 mod executor_data;
 mod executor_range;
+mod playback;
 
-pub use executor::{ButtonExecutor, Executor, FaderExecutor};
-pub use executor_data::{ButtonData, FaderData, Ma2Data};
+pub use command::{Command, CueAction, CueCommand, CueNumber, Playback};
+pub use desk_data::{DataKey, DeskData};
+pub use executor::{ButtonExecutor, Executor, FaderExecutor, BUTTON_IDS, FADER_IDS};
+pub use executor_data::{ButtonData, ButtonState, FaderButton, FaderData, Ma2Data};
 pub use executor_range::{ButtonRange, FaderRange};
+pub use playback::{
+    BlockButton, BlockFader, CueInfo, CueStatus, ExecutorBlock, ExecutorChanges, PlaybackInfo,
+};
@@ -0,0 +1,212 @@
+use super::{executor::Executor, executor_data::ButtonState};
+
+/// Information the desk sends for every executor, independent of its type
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PlaybackInfo {
+    /// Short name of the assigned object type (`oType`), e.g. `LT` or `Ra`, empty if nothing is
+    /// assigned
+    pub object_type: String,
+    /// Number of the assigned object (`oI`), e.g. `13` for a sequence or `4.8` for a rate master
+    pub object_index: String,
+    /// Colour of the executor name (`tt.c`)
+    pub text_color: Option<String>,
+    /// Background colour of the executor (`bC`)
+    pub background_color: Option<String>,
+    /// Background colour of the cue area (`cues.bC`)
+    pub cue_color: Option<String>,
+    /// Cues as shown on the executor, the first one is the current cue
+    pub cues: Vec<CueInfo>,
+    /// Number of executors combined into this one (`combinedItems`)
+    pub combined_items: u16,
+    pub running: bool,
+}
+
+impl PlaybackInfo {
+    /// `true` if no object is assigned to the executor
+    pub fn is_empty(&self) -> bool {
+        self.object_type.is_empty() && self.object_index.is_empty()
+    }
+
+    /// The current cue, `None` if the executor shows no cue
+    pub fn current_cue(&self) -> Option<&CueInfo> {
+        self.cues.first().filter(|cue| !cue.text.is_empty())
+    }
+
+    /// The cue the next `Go` fades to, shown below the current cue
+    pub fn next_cue(&self) -> Option<&CueInfo> {
+        self.cues.get(1).filter(|cue| !cue.text.is_empty())
+    }
+
+    /// Fade progress of the current cue from `0.0` to `1.0`, `None` while no fade is running
+    pub fn fade_progress(&self) -> Option<f32> {
+        self.current_cue()?.progress
+    }
+}
+
+/// Current and next cue of the sequence assigned to an executor, see
+/// [`crate::interface::MaEvent::CueChanged`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CueStatus {
+    pub executor: Executor,
+    pub current: Option<CueInfo>,
+    pub next: Option<CueInfo>,
+}
+
+impl CueStatus {
+    pub fn new(executor: impl Into<Executor>, info: &PlaybackInfo) -> Self {
+        Self {
+            executor: executor.into(),
+            current: info.current_cue().cloned(),
+            next: info.next_cue().cloned(),
+        }
+    }
+
+    /// Fade progress of the current cue from `0.0` to `1.0`
+    pub fn progress(&self) -> Option<f32> {
+        self.current.as_ref()?.progress
+    }
+
+    /// `true` if the executor shows no cues, e.g. because no sequence is assigned
+    pub fn is_empty(&self) -> bool {
+        self.current.is_none() && self.next.is_none()
+    }
+}
+
+/// A single cue line of an executor
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CueInfo {
+    /// Text as shown by the desk, e.g. `1 Cue` or `80.6 BPM` for a speed master
+    pub text: String,
+    pub color: Option<String>,
+    /// Fade progress from `0.0` to `1.0` while the cue is fading in
+    pub progress: Option<f32>,
+}
+
+impl CueInfo {
+    /// Cue number at the start of the text, `None` if the text does not start with a number
+    pub fn number(&self) -> Option<f32> {
+        self.text.split_whitespace().next()?.parse().ok()
+    }
+
+    /// Cue name without the cue number
+    pub fn name(&self) -> &str {
+        match self.number() {
+            Some(_) => self
+                .text
+                .trim_start()
+                .split_once(char::is_whitespace)
+                .map_or("", |(_, name)| name.trim_start()),
+            None => self.text.trim(),
+        }
+    }
+}
+
+/// One fader with its three buttons, a fader executor consists of at least one block
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExecutorBlock {
+    pub button1: BlockButton,
+    pub button2: BlockButton,
+    pub button3: BlockButton,
+    pub fader: BlockFader,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockButton {
+    /// Function of the button as shown by the desk, e.g. `Flash`
+    pub text: String,
+    pub state: ButtonState,
+    pub color: Option<String>,
+}
+
+impl Default for BlockButton {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            state: ButtonState::Released,
+            color: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BlockFader {
+    /// Function of the fader as shown by the desk, e.g. `Mstr`
+    pub label: Option<String>,
+    pub value: f32,
+    /// Value as shown by the desk, e.g. `100%`
+    pub value_text: Option<String>,
+    pub min: f32,
+    pub max: f32,
+    pub touched: bool,
+}
+
+/// Which parts of an executor changed between two updates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExecutorChanges {
+    pub name: bool,
+    pub color: bool,
+    pub cues: bool,
+    pub running: bool,
+    pub value: bool,
+    pub buttons: bool,
+    /// Anything else, e.g. the assigned object or the texts of the fader blocks
+    pub other: bool,
+}
+
+impl ExecutorChanges {
+    pub fn any(&self) -> bool {
+        self.name
+            || self.color
+            || self.cues
+            || self.running
+            || self.value
+            || self.buttons
+            || self.other
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cue_info() {
+        let cue = |text: &str| CueInfo {
+            text: text.to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(cue("2 Chorus").number(), Some(2.0));
+        assert_eq!(cue("2 Chorus").name(), "Chorus");
+        assert_eq!(cue("1.5  Drop it").name(), "Drop it");
+        assert_eq!(cue("3").name(), "");
+        assert_eq!(cue("    1 Cue").number(), Some(1.0));
+        assert_eq!(cue("    1 Cue").name(), "Cue");
+        assert_eq!(cue("100%").number(), None);
+        assert_eq!(cue("100%").name(), "100%");
+    }
+
+    #[test]
+    fn test_cue_status() {
+        let cue = |text: &str, progress| CueInfo {
+            text: text.to_owned(),
+            progress,
+            ..Default::default()
+        };
+        let info = PlaybackInfo {
+            cues: vec![cue("2 Verse", Some(0.25)), cue("3 Chorus", None)],
+            ..Default::default()
+        };
+        let status = CueStatus::new(Executor::new(1, 1), &info);
+        assert_eq!(status.current.as_ref().and_then(CueInfo::number), Some(2.0));
+        assert_eq!(status.next.as_ref().map(CueInfo::name), Some("Chorus"));
+        assert_eq!(status.progress(), Some(0.25));
+        assert_eq!(info.fade_progress(), Some(0.25));
+
+        let info = PlaybackInfo {
+            cues: vec![cue("", Some(1.0)), cue("", None)],
+            ..Default::default()
+        };
+        assert!(CueStatus::new(Executor::new(1, 1), &info).is_empty());
+        assert_eq!(info.fade_progress(), None);
+    }
+}
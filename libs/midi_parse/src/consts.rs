@@ -14,6 +14,8 @@ pub const CHANNEL_PRESSURE_MASK: u8 = 0b1101_0000;
 pub const PITCH_WHEEL_CHANGE_MASK: u8 = 0b1110_0000;
 
 // All these messages start with 0b1111, the 2nd 4 bits are part of the status
+pub const SYSTEM_MASK: u8 = 0b1111_0000;
+pub const MTC_QUARTER_FRAME_MASK: u8 = 0b1111_0001;
 pub const SONG_POSITION_POINTER_MASK: u8 = 0b1111_0010;
 pub const SONG_SELECT_MASK: u8 = 0b1111_0011;
 pub const TIMING_CLOCK_MASK: u8 = 0b1111_1000;
@@ -26,3 +28,9 @@ pub const TUNE_REQUEST_MASK: u8 = 0b1111_0110;
 
 pub const SYSEX_MESSAGE_MASK: u8 = 0b1111_0000;
 pub const SYSEX_MESSAGE_END_MASK: u8 = 0b11110111;
+
+// Universal SysEx messages start with one of these ids, followed by the device id
+pub const UNIVERSAL_NON_REALTIME: u8 = 0x7E;
+pub const UNIVERSAL_REALTIME: u8 = 0x7F;
+/// Device id that addresses all devices
+pub const ALL_DEVICES: u8 = 0x7F;
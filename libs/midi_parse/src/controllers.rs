@@ -0,0 +1,244 @@
+use crate::types::u14;
+use crate::{Channel, Controller, MidiMessage, Value};
+
+/// Controllers 0 to 31 send the MSB of a value, the controller this much higher the LSB
+const LSB_OFFSET: u8 = 32;
+const DATA_ENTRY_MSB: u8 = 6;
+const DATA_ENTRY_LSB: u8 = DATA_ENTRY_MSB + LSB_OFFSET;
+const NRPN_LSB: u8 = 98;
+const NRPN_MSB: u8 = 99;
+const RPN_LSB: u8 = 100;
+const RPN_MSB: u8 = 101;
+/// Selecting this parameter deselects the current one
+const NULL_PARAMETER: u16 = 0x3FFF;
+
+/// A controller value, values of pairs and parameters have 14 bits
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum ControllerEvent {
+    /// A controller that is not part of a pair or a parameter
+    Cc {
+        channel: Channel,
+        controller: Controller,
+        value: Value,
+    },
+    /// A pair of controllers, `controller` is the one from `0` to `31` sending the MSB
+    ///
+    /// Higher controllers are truncated to that range when sending.
+    Cc14 {
+        channel: Channel,
+        controller: Controller,
+        value: u16,
+    },
+    /// A registered parameter like the pitch bend range
+    Rpn {
+        channel: Channel,
+        param: u16,
+        value: u16,
+    },
+    /// A parameter specific to the device
+    Nrpn {
+        channel: Channel,
+        param: u16,
+        value: u16,
+    },
+}
+
+impl ControllerEvent {
+    /// The control changes sending this event, values are truncated to 7 or 14 bits
+    pub fn to_messages(&self) -> Vec<MidiMessage> {
+        let control_change =
+            |channel: &Channel, controller: u8, value: u8| MidiMessage::ControlChange {
+                channel: channel.clone(),
+                controller_number: Controller(controller & 0b0111_1111),
+                value: Value(value & 0b0111_1111),
+            };
+        let msb = |value: u16| (value >> 7) as u8;
+        let lsb = |value: u16| value as u8;
+        match self {
+            ControllerEvent::Cc {
+                channel,
+                controller,
+                value,
+            } => vec![control_change(channel, controller.0, value.0)],
+            ControllerEvent::Cc14 {
+                channel,
+                controller,
+                value,
+            } => {
+                let controller = controller.0 % LSB_OFFSET;
+                vec![
+                    control_change(channel, controller, msb(*value)),
+                    control_change(channel, controller + LSB_OFFSET, lsb(*value)),
+                ]
+            }
+            ControllerEvent::Rpn {
+                channel,
+                param,
+                value,
+            } => vec![
+                control_change(channel, RPN_MSB, msb(*param)),
+                control_change(channel, RPN_LSB, lsb(*param)),
+                control_change(channel, DATA_ENTRY_MSB, msb(*value)),
+                control_change(channel, DATA_ENTRY_LSB, lsb(*value)),
+            ],
+            ControllerEvent::Nrpn {
+                channel,
+                param,
+                value,
+            } => vec![
+                control_change(channel, NRPN_MSB, msb(*param)),
+                control_change(channel, NRPN_LSB, lsb(*param)),
+                control_change(channel, DATA_ENTRY_MSB, msb(*value)),
+                control_change(channel, DATA_ENTRY_LSB, lsb(*value)),
+            ],
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+enum ParameterKind {
+    Registered,
+    NonRegistered,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ChannelState {
+    /// The last MSB of each pair
+    msb: [Option<u8>; LSB_OFFSET as usize],
+    /// MSB and LSB of the selected parameters
+    rpn: [Option<u8>; 2],
+    nrpn: [Option<u8>; 2],
+    /// The kind of parameter that has been selected last
+    selected: Option<ParameterKind>,
+    data_msb: Option<u8>,
+}
+
+impl ChannelState {
+    fn select(&mut self, controller: u8, value: u8) {
+        let (kind, bytes, index) = match controller {
+            RPN_MSB => (ParameterKind::Registered, &mut self.rpn, 0),
+            RPN_LSB => (ParameterKind::Registered, &mut self.rpn, 1),
+            NRPN_MSB => (ParameterKind::NonRegistered, &mut self.nrpn, 0),
+            _ => (ParameterKind::NonRegistered, &mut self.nrpn, 1),
+        };
+        bytes[index] = Some(value);
+        self.selected = Some(kind);
+        self.data_msb = None;
+    }
+
+    fn parameter(&self) -> Option<(ParameterKind, u16)> {
+        let kind = self.selected?;
+        let [Some(msb), Some(lsb)] = (match kind {
+            ParameterKind::Registered => self.rpn,
+            ParameterKind::NonRegistered => self.nrpn,
+        }) else {
+            return None;
+        };
+        let param = u14([lsb, msb]);
+        (param != NULL_PARAMETER).then_some((kind, param))
+    }
+}
+
+/// Merges control changes into pairs and parameters with 14 bit values, per channel
+///
+/// An MSB is reported right away, as the LSB is optional and resets to `0` with every MSB.
+/// Controllers that are selecting parameters are not reported.
+#[derive(Debug)]
+pub struct ControllerAggregator {
+    /// Bits of the controllers from `0` to `31` that are merged with their LSB
+    pairs: u32,
+    channels: [ChannelState; 16],
+}
+
+impl Default for ControllerAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ControllerAggregator {
+    /// Merges all controllers from `0` to `31` with their LSB
+    pub fn new() -> Self {
+        Self::with_pairs(0..LSB_OFFSET)
+    }
+
+    /// Only merges the given controllers, for devices using others as separate 7 bit controls
+    pub fn with_pairs(controllers: impl IntoIterator<Item = u8>) -> Self {
+        let pairs = controllers
+            .into_iter()
+            .filter(|controller| *controller < LSB_OFFSET)
+            .fold(0, |pairs, controller| pairs | 1 << controller);
+        Self {
+            pairs,
+            channels: [ChannelState::default(); 16],
+        }
+    }
+
+    /// Messages other than control changes are ignored
+    pub fn process(&mut self, message: &MidiMessage) -> Option<ControllerEvent> {
+        let MidiMessage::ControlChange {
+            channel,
+            controller_number,
+            value,
+        } = message
+        else {
+            return None;
+        };
+        let state = &mut self.channels[(channel.0 & 0b1111) as usize];
+        let is_pair = |controller: u8| self.pairs & 1 << controller != 0;
+        let channel = channel.clone();
+
+        match controller_number.0 {
+            RPN_MSB | RPN_LSB | NRPN_MSB | NRPN_LSB => {
+                state.select(controller_number.0, value.0);
+                None
+            }
+            controller @ (DATA_ENTRY_MSB | DATA_ENTRY_LSB) if state.parameter().is_some() => {
+                let (kind, param) = state.parameter()?;
+                let value = if controller == DATA_ENTRY_MSB {
+                    state.data_msb = Some(value.0);
+                    u14([0, value.0])
+                } else {
+                    u14([value.0, state.data_msb.unwrap_or_default()])
+                };
+                Some(match kind {
+                    ParameterKind::Registered => ControllerEvent::Rpn {
+                        channel,
+                        param,
+                        value,
+                    },
+                    ParameterKind::NonRegistered => ControllerEvent::Nrpn {
+                        channel,
+                        param,
+                        value,
+                    },
+                })
+            }
+            controller if controller < LSB_OFFSET && is_pair(controller) => {
+                state.msb[controller as usize] = Some(value.0);
+                Some(ControllerEvent::Cc14 {
+                    channel,
+                    controller: Controller(controller),
+                    value: u14([0, value.0]),
+                })
+            }
+            controller
+                if (LSB_OFFSET..2 * LSB_OFFSET).contains(&controller)
+                    && is_pair(controller - LSB_OFFSET)
+                    && state.msb[(controller - LSB_OFFSET) as usize].is_some() =>
+            {
+                let msb = state.msb[(controller - LSB_OFFSET) as usize]?;
+                Some(ControllerEvent::Cc14 {
+                    channel,
+                    controller: Controller(controller - LSB_OFFSET),
+                    value: u14([value.0, msb]),
+                })
+            }
+            controller => Some(ControllerEvent::Cc {
+                channel,
+                controller: Controller(controller),
+                value: value.clone(),
+            }),
+        }
+    }
+}
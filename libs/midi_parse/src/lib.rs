@@ -1,8 +1,16 @@
 mod consts;
+mod controllers;
 mod message;
+pub mod msc;
+mod mtc;
+mod stream;
 mod types;
+pub mod universal;
 
+pub use controllers::{ControllerAggregator, ControllerEvent};
 pub use message::{MidiMessage, MidiMessageError};
+pub use mtc::{Direction, FrameRate, MtcDecoder, MtcEvent, Timecode};
+pub use stream::{StreamEncoder, StreamParser};
 pub use types::*;
 
 // TODO: Write tests
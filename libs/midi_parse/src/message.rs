@@ -37,6 +37,10 @@ pub enum MidiMessage {
         pitch: Pitch,
     },
     SysExMessage(Box<[u8]>),
+    /// One of eight pieces of a MIDI Time Code, see [`crate::MtcDecoder`]
+    QuarterFrame {
+        piece: TimecodePiece,
+    },
     SongPositionPointer {
         position: Position,
     },
@@ -56,8 +60,17 @@ pub enum MidiMessage {
 impl MidiMessage {
     pub fn from(data: &[u8]) -> Result<MidiMessage, MidiMessageError> {
         let length = data.len();
-        let channel = Channel::from(data[0] & CHANNEL_MASK)?;
-        match data[0] & STATUS_BYTE_MASK {
+        let status = *data.first().ok_or(MidiMessageError::InvalidLength)?;
+        if length < message_length(status) {
+            return Err(MidiMessageError::InvalidLength);
+        }
+        let channel = Channel::from(status & CHANNEL_MASK)?;
+        // System messages use the whole status byte, there is no channel
+        let code = match status & STATUS_BYTE_MASK {
+            SYSTEM_MASK => status,
+            code => code,
+        };
+        match code {
             NOTE_OFF_MASK => Ok(MidiMessage::NoteOff {
                 channel,
                 note: Note::from(data[1])?,
@@ -101,6 +114,9 @@ impl MidiMessage {
                 let sysex_message: &[u8] = &data[1..=length - 2];
                 Ok(MidiMessage::SysExMessage(sysex_message.into()))
             }
+            MTC_QUARTER_FRAME_MASK => Ok(MidiMessage::QuarterFrame {
+                piece: TimecodePiece::from(data[1])?,
+            }),
             SONG_POSITION_POINTER_MASK => Ok(MidiMessage::SongPositionPointer {
                 position: Position::from(data[1], data[2])?,
             }),
@@ -185,6 +201,7 @@ impl MidiMessage {
                 [PITCH_WHEEL_CHANGE_MASK | channel.to_byte(), byte2, byte3].into()
             }
             MM::SysExMessage(message) => [&[SYSEX_MESSAGE_MASK], &message[..], &[SYSEX_MESSAGE_END_MASK]].concat().into(),
+            MM::QuarterFrame { piece } => Box::new([MTC_QUARTER_FRAME_MASK, piece.to_byte()]),
             MM::SongPositionPointer { position } => {
                 let [byte2, byte3] = position.to_bytes();
                 Box::new([SONG_POSITION_POINTER_MASK, byte2, byte3])
@@ -214,6 +231,7 @@ impl MidiMessage {
             MM::ChannelPressure { .. } => 2,
             MM::PitchWheelChange { .. } => 3,
             MM::SysExMessage(inner) => 2 + inner.len(),
+            MM::QuarterFrame { .. } => 2,
             MM::SongPositionPointer { .. } => 3,
             MM::SongSelect { .. } => 2,
             MM::TuneRequest => 1,
@@ -227,6 +245,22 @@ impl MidiMessage {
     }
 }
 
+/// The length in bytes of a message starting with `status`, the minimum length for SysEx
+pub(crate) fn message_length(status: u8) -> usize {
+    match status & STATUS_BYTE_MASK {
+        PROGRAM_CHANGE_MASK | CHANNEL_PRESSURE_MASK => 2,
+        SYSTEM_MASK => match status {
+            SYSEX_MESSAGE_MASK => 2,
+            MTC_QUARTER_FRAME_MASK | SONG_SELECT_MASK => 2,
+            SONG_POSITION_POINTER_MASK => 3,
+            _ => 1,
+        },
+        code if code & 0b1000_0000 != 0 => 3,
+        // Data bytes are not a message on their own
+        _ => 1,
+    }
+}
+
 #[derive(Debug)]
 pub enum MidiMessageError {
     InvalidChannel(u8),
@@ -239,6 +273,9 @@ pub enum MidiMessageError {
     InvalidPitch(u8, u8),
     InvalidPosition(u8, u8),
     InvalidSong(u8),
+    InvalidTimecodePiece(u8),
     InvalidCode(u8),
     InvalidLength,
+    /// A data byte without a preceding status byte
+    UnexpectedData(u8),
 }
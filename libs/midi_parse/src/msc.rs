@@ -0,0 +1,314 @@
+//! MIDI Show Control, used to trigger cues of lighting desks and other show control devices
+//!
+//! MSC messages are SysEx messages, [`MscMessage::from_sysex`] and [`MscMessage::to_sysex`]
+//! convert them from and to [`MidiMessage::SysExMessage`].
+
+use crate::consts::{ALL_DEVICES, UNIVERSAL_REALTIME};
+use crate::types::{u14, u14_bytes};
+use crate::{MidiMessage, Timecode};
+
+/// Sub id of MSC messages
+const MSC_SUB_ID: u8 = 0x02;
+/// Separates the cue number, list and path
+const CUE_DELIMITER: u8 = 0x00;
+
+/// Device id that addresses all devices
+pub const ALL_CALL: u8 = ALL_DEVICES;
+
+const GO: u8 = 0x01;
+const STOP: u8 = 0x02;
+const RESUME: u8 = 0x03;
+const TIMED_GO: u8 = 0x04;
+const LOAD: u8 = 0x05;
+const SET: u8 = 0x06;
+const FIRE: u8 = 0x07;
+const ALL_OFF: u8 = 0x08;
+const RESTORE: u8 = 0x09;
+const RESET: u8 = 0x0A;
+const GO_OFF: u8 = 0x0B;
+
+#[derive(Debug)]
+pub enum MscError {
+    /// The message is not an MSC message
+    NotMsc,
+    InvalidCommand(u8),
+    InvalidCueNumber,
+    InvalidTime,
+    InvalidLength,
+}
+
+/// The kind of device a message is meant for
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum CommandFormat {
+    Lighting,
+    MovingLights,
+    Sound,
+    Machinery,
+    Video,
+    Projection,
+    ProcessControl,
+    Pyro,
+    AllTypes,
+    /// One of the more specific formats, e.g. `0x12` for CD players
+    Other(u8),
+}
+
+impl CommandFormat {
+    fn from_byte(byte: u8) -> CommandFormat {
+        match byte {
+            0x01 => CommandFormat::Lighting,
+            0x02 => CommandFormat::MovingLights,
+            0x10 => CommandFormat::Sound,
+            0x20 => CommandFormat::Machinery,
+            0x30 => CommandFormat::Video,
+            0x40 => CommandFormat::Projection,
+            0x50 => CommandFormat::ProcessControl,
+            0x60 => CommandFormat::Pyro,
+            0x7F => CommandFormat::AllTypes,
+            byte => CommandFormat::Other(byte),
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            CommandFormat::Lighting => 0x01,
+            CommandFormat::MovingLights => 0x02,
+            CommandFormat::Sound => 0x10,
+            CommandFormat::Machinery => 0x20,
+            CommandFormat::Video => 0x30,
+            CommandFormat::Projection => 0x40,
+            CommandFormat::ProcessControl => 0x50,
+            CommandFormat::Pyro => 0x60,
+            CommandFormat::AllTypes => 0x7F,
+            CommandFormat::Other(byte) => byte & 0b0111_1111,
+        }
+    }
+}
+
+/// A cue number like `"1.5"`, optionally in a cue list and a cue path
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Cue {
+    number: String,
+    list: Option<String>,
+    path: Option<String>,
+}
+
+impl Cue {
+    /// Numbers may only contain digits and a decimal point
+    pub fn new(number: &str, list: Option<&str>, path: Option<&str>) -> Result<Cue, MscError> {
+        let parse = |number: &str| {
+            let valid = !number.is_empty()
+                && number
+                    .bytes()
+                    .all(|byte| byte.is_ascii_digit() || byte == b'.');
+            valid
+                .then(|| number.to_string())
+                .ok_or(MscError::InvalidCueNumber)
+        };
+        // A path is only valid within a list
+        if list.is_none() && path.is_some() {
+            return Err(MscError::InvalidCueNumber);
+        }
+        Ok(Cue {
+            number: parse(number)?,
+            list: list.map(parse).transpose()?,
+            path: path.map(parse).transpose()?,
+        })
+    }
+
+    pub fn number(&self) -> &str {
+        &self.number
+    }
+
+    pub fn list(&self) -> Option<&str> {
+        self.list.as_deref()
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Parses the number, list and path separated by zero bytes, `None` if there is no cue
+    fn from_bytes(data: &[u8]) -> Result<Option<Cue>, MscError> {
+        // Some devices terminate the last field as well
+        let data = data.strip_suffix(&[CUE_DELIMITER]).unwrap_or(data);
+        if data.is_empty() {
+            return Ok(None);
+        }
+        let mut fields = data
+            .split(|&byte| byte == CUE_DELIMITER)
+            .map(|field| std::str::from_utf8(field).map_err(|_| MscError::InvalidCueNumber));
+        let number = fields.next().transpose()?.unwrap_or_default();
+        let list = fields.next().transpose()?;
+        let path = fields.next().transpose()?;
+        if fields.next().is_some() {
+            return Err(MscError::InvalidCueNumber);
+        }
+        Cue::new(number, list, path).map(Some)
+    }
+
+    fn write_bytes(&self, data: &mut Vec<u8>) {
+        data.extend_from_slice(self.number.as_bytes());
+        for field in [&self.list, &self.path].into_iter().flatten() {
+            data.push(CUE_DELIMITER);
+            data.extend_from_slice(field.as_bytes());
+        }
+    }
+}
+
+/// Commands without a cue apply to the current cue or all cues, depending on the device
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum MscCommand {
+    Go(Option<Cue>),
+    Stop(Option<Cue>),
+    Resume(Option<Cue>),
+    /// Go with the given fade time instead of the time of the cue
+    TimedGo(Timecode, Option<Cue>),
+    Load(Cue),
+    /// Set a control, e.g. a fader, to a value from `0` to `0x3FFF`, optionally over a time
+    Set {
+        control: u16,
+        value: u16,
+        time: Option<Timecode>,
+    },
+    /// Fire a macro from `0` to `127`
+    Fire(u8),
+    AllOff,
+    Restore,
+    Reset,
+    GoOff(Option<Cue>),
+}
+
+/// An MSC command for a device
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct MscMessage {
+    /// Device id from `0` to `0x6F`, a group from `0x70` to `0x7E` or [`ALL_CALL`]
+    pub device: u8,
+    pub format: CommandFormat,
+    pub command: MscCommand,
+}
+
+impl MscMessage {
+    pub fn new(device: u8, format: CommandFormat, command: MscCommand) -> MscMessage {
+        MscMessage {
+            device,
+            format,
+            command,
+        }
+    }
+
+    pub fn from_sysex(message: &MidiMessage) -> Result<MscMessage, MscError> {
+        let MidiMessage::SysExMessage(data) = message else {
+            return Err(MscError::NotMsc);
+        };
+        let [UNIVERSAL_REALTIME, device, MSC_SUB_ID, format, command, data @ ..] = &data[..] else {
+            return Err(MscError::NotMsc);
+        };
+
+        let command = match *command {
+            GO => MscCommand::Go(Cue::from_bytes(data)?),
+            STOP => MscCommand::Stop(Cue::from_bytes(data)?),
+            RESUME => MscCommand::Resume(Cue::from_bytes(data)?),
+            TIMED_GO => {
+                let (time, cue) = data.split_at_checked(5).ok_or(MscError::InvalidLength)?;
+                MscCommand::TimedGo(parse_time(time)?, Cue::from_bytes(cue)?)
+            }
+            LOAD => MscCommand::Load(Cue::from_bytes(data)?.ok_or(MscError::InvalidLength)?),
+            SET => {
+                let (values, time) = data.split_at_checked(4).ok_or(MscError::InvalidLength)?;
+                let time = match time {
+                    [] => None,
+                    time => Some(parse_time(time)?),
+                };
+                MscCommand::Set {
+                    control: u14([values[0], values[1]]),
+                    value: u14([values[2], values[3]]),
+                    time,
+                }
+            }
+            FIRE => match data {
+                [macro_number] => MscCommand::Fire(*macro_number),
+                _ => return Err(MscError::InvalidLength),
+            },
+            ALL_OFF => MscCommand::AllOff,
+            RESTORE => MscCommand::Restore,
+            RESET => MscCommand::Reset,
+            GO_OFF => MscCommand::GoOff(Cue::from_bytes(data)?),
+            command => return Err(MscError::InvalidCommand(command)),
+        };
+
+        Ok(MscMessage {
+            device: *device,
+            format: CommandFormat::from_byte(*format),
+            command,
+        })
+    }
+
+    /// Values outside of their ranges are truncated to 7 or 14 bits
+    pub fn to_sysex(&self) -> MidiMessage {
+        let mut data = vec![
+            UNIVERSAL_REALTIME,
+            self.device & 0b0111_1111,
+            MSC_SUB_ID,
+            self.format.to_byte(),
+        ];
+        let write_cue = |data: &mut Vec<u8>, cue: &Option<Cue>| {
+            if let Some(cue) = cue {
+                cue.write_bytes(data);
+            }
+        };
+        match &self.command {
+            MscCommand::Go(cue) => {
+                data.push(GO);
+                write_cue(&mut data, cue);
+            }
+            MscCommand::Stop(cue) => {
+                data.push(STOP);
+                write_cue(&mut data, cue);
+            }
+            MscCommand::Resume(cue) => {
+                data.push(RESUME);
+                write_cue(&mut data, cue);
+            }
+            MscCommand::TimedGo(time, cue) => {
+                data.push(TIMED_GO);
+                data.extend_from_slice(&time.to_time_bytes());
+                write_cue(&mut data, cue);
+            }
+            MscCommand::Load(cue) => {
+                data.push(LOAD);
+                cue.write_bytes(&mut data);
+            }
+            MscCommand::Set {
+                control,
+                value,
+                time,
+            } => {
+                data.push(SET);
+                data.extend_from_slice(&u14_bytes(*control));
+                data.extend_from_slice(&u14_bytes(*value));
+                if let Some(time) = time {
+                    data.extend_from_slice(&time.to_time_bytes());
+                }
+            }
+            MscCommand::Fire(macro_number) => {
+                data.extend_from_slice(&[FIRE, macro_number & 0b0111_1111])
+            }
+            MscCommand::AllOff => data.push(ALL_OFF),
+            MscCommand::Restore => data.push(RESTORE),
+            MscCommand::Reset => data.push(RESET),
+            MscCommand::GoOff(cue) => {
+                data.push(GO_OFF);
+                write_cue(&mut data, cue);
+            }
+        }
+        MidiMessage::SysExMessage(data.into())
+    }
+}
+
+fn parse_time(data: &[u8]) -> Result<Timecode, MscError> {
+    if data.len() != 5 {
+        return Err(MscError::InvalidLength);
+    }
+    Timecode::from_time_bytes(data).ok_or(MscError::InvalidTime)
+}
@@ -0,0 +1,300 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::consts::{ALL_DEVICES, UNIVERSAL_REALTIME};
+use crate::{MidiMessage, TimecodePiece};
+
+/// Sub ids of a full frame message
+const FULL_FRAME: [u8; 2] = [0x01, 0x01];
+/// Quarter frames are sent every quarter of a frame, so this is a few missing frames
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum FrameRate {
+    Fps24,
+    Fps25,
+    /// 29.97 frames per second, frame numbers are skipped to keep up with clock time
+    Fps2997Drop,
+    Fps30,
+}
+
+impl FrameRate {
+    fn from_code(code: u8) -> FrameRate {
+        match code & 0b11 {
+            0 => FrameRate::Fps24,
+            1 => FrameRate::Fps25,
+            2 => FrameRate::Fps2997Drop,
+            _ => FrameRate::Fps30,
+        }
+    }
+
+    fn code(&self) -> u8 {
+        match self {
+            FrameRate::Fps24 => 0,
+            FrameRate::Fps25 => 1,
+            FrameRate::Fps2997Drop => 2,
+            FrameRate::Fps30 => 3,
+        }
+    }
+
+    /// The number of frames counted per second
+    pub fn frames(&self) -> u8 {
+        match self {
+            FrameRate::Fps24 => 24,
+            FrameRate::Fps25 => 25,
+            FrameRate::Fps2997Drop | FrameRate::Fps30 => 30,
+        }
+    }
+}
+
+/// A position of MIDI Time Code
+///
+/// Values outside of their ranges are truncated to 5 or 6 bits when sending.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Timecode {
+    pub hours: u8,
+    pub minutes: u8,
+    pub seconds: u8,
+    pub frames: u8,
+    pub rate: FrameRate,
+}
+
+impl Timecode {
+    /// Reads a full frame SysEx message, `None` for any other message
+    pub fn from_full_frame(message: &MidiMessage) -> Option<Timecode> {
+        let MidiMessage::SysExMessage(data) = message else {
+            return None;
+        };
+        match **data {
+            [UNIVERSAL_REALTIME, _device, sub_id1, sub_id2, hours, minutes, seconds, frames]
+                if [sub_id1, sub_id2] == FULL_FRAME =>
+            {
+                Timecode::from_parts(hours, minutes, seconds, frames)
+            }
+            _ => None,
+        }
+    }
+
+    /// A full frame message for all devices, which locates to this timecode without running
+    pub fn to_full_frame(&self) -> MidiMessage {
+        let [sub_id1, sub_id2] = FULL_FRAME;
+        let [hours, minutes, seconds, frames] = self.to_parts();
+        MidiMessage::SysExMessage(Box::new([
+            UNIVERSAL_REALTIME,
+            ALL_DEVICES,
+            sub_id1,
+            sub_id2,
+            hours,
+            minutes,
+            seconds,
+            frames,
+        ]))
+    }
+
+    /// The eight quarter frames that send this timecode while running forward
+    pub fn to_quarter_frames(&self) -> [MidiMessage; 8] {
+        // Low and high nibbles, starting with the frames
+        let parts = self.to_parts();
+        std::array::from_fn(|index| MidiMessage::QuarterFrame {
+            piece: TimecodePiece::new(index as u8, parts[3 - index / 2] >> (index % 2 * 4)),
+        })
+    }
+
+    /// Reads the time of MSC and MMC messages, which has an additional byte for fractional frames
+    pub(crate) fn from_time_bytes(data: &[u8]) -> Option<Timecode> {
+        let [hours, minutes, seconds, frames, _fractional_frames] = *data else {
+            return None;
+        };
+        // The upper bits of the frames are flags
+        Timecode::from_parts(hours, minutes, seconds, frames & 0b1_1111)
+    }
+
+    pub(crate) fn to_time_bytes(self) -> [u8; 5] {
+        let [hours, minutes, seconds, frames] = self.to_parts();
+        [hours, minutes, seconds, frames, 0]
+    }
+
+    /// Hours with the frame rate, minutes, seconds and frames, each within 7 bits
+    fn to_parts(self) -> [u8; 4] {
+        [
+            self.rate.code() << 5 | self.hours & 0b1_1111,
+            self.minutes & 0b11_1111,
+            self.seconds & 0b11_1111,
+            self.frames & 0b1_1111,
+        ]
+    }
+
+    fn from_pieces(pieces: &[u8; 8]) -> Option<Timecode> {
+        Timecode::from_parts(
+            pieces[6] | pieces[7] << 4,
+            pieces[4] | pieces[5] << 4,
+            pieces[2] | pieces[3] << 4,
+            pieces[0] | pieces[1] << 4,
+        )
+    }
+
+    /// Bits 5 and 6 of `hours` hold the frame rate, like in the MTC messages
+    fn from_parts(hours: u8, minutes: u8, seconds: u8, frames: u8) -> Option<Timecode> {
+        let timecode = Timecode {
+            hours: hours & 0b1_1111,
+            minutes,
+            seconds,
+            frames,
+            rate: FrameRate::from_code(hours >> 5),
+        };
+        let valid =
+            timecode.hours < 24 && minutes < 60 && seconds < 60 && frames < timecode.rate.frames();
+        valid.then_some(timecode)
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Drop frame timecode is separated by a semicolon by convention
+        let separator = match self.rate {
+            FrameRate::Fps2997Drop => ';',
+            _ => ':',
+        };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{separator}{:02}",
+            self.hours, self.minutes, self.seconds, self.frames
+        )
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Direction {
+    Forward,
+    Backward,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum MtcEvent {
+    /// All eight quarter frames of a timecode have been received
+    ///
+    /// The timecode is the frame in which the first quarter frame has been sent, so it lags
+    /// the sender by two frames.
+    Running(Timecode, Direction),
+    /// A full frame message moved to this timecode without running
+    Located(Timecode),
+    /// No quarter frame has been received within the timeout, the timecode stopped
+    Dropout,
+}
+
+/// Assembles quarter frames and full frame messages into timecodes
+#[derive(Debug)]
+pub struct MtcDecoder {
+    timeout: Duration,
+    /// Values of the quarter frames, the bits of `received` mark the ones that are valid
+    pieces: [u8; 8],
+    received: u8,
+    last_piece: Option<u8>,
+    last_received: Option<Instant>,
+    direction: Direction,
+    timecode: Option<Timecode>,
+}
+
+impl Default for MtcDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MtcDecoder {
+    pub fn new() -> Self {
+        Self::with_timeout(DEFAULT_TIMEOUT)
+    }
+
+    /// Reports a dropout after `timeout` without quarter frames, the default is 100 ms
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            pieces: [0; 8],
+            received: 0,
+            last_piece: None,
+            last_received: None,
+            direction: Direction::Forward,
+            timecode: None,
+        }
+    }
+
+    /// The last timecode that has been received
+    pub fn timecode(&self) -> Option<Timecode> {
+        self.timecode
+    }
+
+    /// Whether quarter frames are being received
+    pub fn is_running(&self) -> bool {
+        self.last_received.is_some()
+    }
+
+    /// Decodes a message received at `now`, other messages than MTC are ignored
+    pub fn decode(&mut self, message: &MidiMessage, now: Instant) -> Option<MtcEvent> {
+        if let Some(timecode) = Timecode::from_full_frame(message) {
+            self.stop();
+            self.timecode = Some(timecode);
+            return Some(MtcEvent::Located(timecode));
+        }
+        let MidiMessage::QuarterFrame { piece } = message else {
+            return None;
+        };
+
+        // Pieces of a timecode that stalled do not belong to the new one
+        if self.is_stalled(now) {
+            self.stop();
+        }
+
+        let index = piece.index();
+        if let Some(last) = self.last_piece {
+            let direction = if index == (last + 1) % 8 {
+                Some(Direction::Forward)
+            } else if index == (last + 7) % 8 {
+                Some(Direction::Backward)
+            } else {
+                None
+            };
+            // Start over if a piece has been missed or the direction changed
+            if direction != Some(self.direction) {
+                self.received = 0;
+            }
+            self.direction = direction.unwrap_or(Direction::Forward);
+        }
+        self.last_piece = Some(index);
+        self.last_received = Some(now);
+        self.pieces[index as usize] = piece.value();
+        self.received |= 1 << index;
+
+        let last_index = match self.direction {
+            Direction::Forward => 7,
+            Direction::Backward => 0,
+        };
+        if index != last_index || self.received != 0xFF {
+            return None;
+        }
+        self.received = 0;
+        let timecode = Timecode::from_pieces(&self.pieces)?;
+        self.timecode = Some(timecode);
+        Some(MtcEvent::Running(timecode, self.direction))
+    }
+
+    /// Reports a dropout once, call this regularly as a stopped sender sends nothing
+    pub fn poll(&mut self, now: Instant) -> Option<MtcEvent> {
+        if !self.is_stalled(now) {
+            return None;
+        }
+        self.stop();
+        Some(MtcEvent::Dropout)
+    }
+
+    fn is_stalled(&self, now: Instant) -> bool {
+        self.last_received
+            .is_some_and(|last| now.saturating_duration_since(last) > self.timeout)
+    }
+
+    fn stop(&mut self) {
+        self.received = 0;
+        self.last_piece = None;
+        self.last_received = None;
+    }
+}
@@ -0,0 +1,148 @@
+use crate::consts::*;
+use crate::message::{message_length, MidiMessage, MidiMessageError};
+
+/// Longer than common dumps, while a lost end byte cannot take up all memory
+const DEFAULT_MAX_SYSEX: usize = 0x10000;
+
+/// Parses MIDI messages from a byte stream such as a serial port, USB payloads or a file
+///
+/// Chunks may split messages anywhere. Running status, realtime bytes inside other messages
+/// and SysEx messages spanning several chunks are handled.
+#[derive(Debug)]
+pub struct StreamParser {
+    /// Status of the last channel message, used for data bytes without a status byte
+    running_status: Option<u8>,
+    /// The incomplete message, starting with its status byte
+    buffer: Vec<u8>,
+    max_sysex: usize,
+    /// The rest of a SysEx message that has been too long is skipped
+    skipping_sysex: bool,
+}
+
+impl Default for StreamParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamParser {
+    pub fn new() -> Self {
+        Self::with_max_sysex(DEFAULT_MAX_SYSEX)
+    }
+
+    /// Drops SysEx messages with more than `max_sysex` data bytes, the default is 64 KiB
+    ///
+    /// Longer messages are reported once as [`MidiMessageError::InvalidLength`].
+    pub fn with_max_sysex(max_sysex: usize) -> Self {
+        Self {
+            running_status: None,
+            buffer: Vec::new(),
+            max_sysex,
+            skipping_sysex: false,
+        }
+    }
+
+    /// Parses the next chunk, incomplete messages are completed by the following chunks
+    pub fn parse(&mut self, data: &[u8]) -> Vec<Result<MidiMessage, MidiMessageError>> {
+        let mut messages = Vec::new();
+        for &byte in data {
+            self.parse_byte(byte, &mut messages);
+        }
+        messages
+    }
+
+    /// Drops the incomplete message and the running status, e.g. after reconnecting a device
+    pub fn reset(&mut self) {
+        self.running_status = None;
+        self.buffer.clear();
+        self.skipping_sysex = false;
+    }
+
+    fn parse_byte(&mut self, byte: u8, messages: &mut Vec<Result<MidiMessage, MidiMessageError>>) {
+        if byte >= TIMING_CLOCK_MASK {
+            // Realtime messages may appear anywhere, even inside other messages
+            messages.push(MidiMessage::from(&[byte]));
+            return;
+        }
+
+        if byte & 0b1000_0000 != 0 {
+            if std::mem::take(&mut self.skipping_sysex) && byte == SYSEX_MESSAGE_END_MASK {
+                return;
+            }
+            if byte == SYSEX_MESSAGE_END_MASK && self.buffer.first() == Some(&SYSEX_MESSAGE_MASK) {
+                self.buffer.push(byte);
+                messages.push(MidiMessage::from(&self.buffer));
+                self.buffer.clear();
+                return;
+            }
+            if !self.buffer.is_empty() {
+                // The previous message has been interrupted by this one
+                self.buffer.clear();
+                messages.push(Err(MidiMessageError::InvalidLength));
+            }
+            // Only channel messages can be repeated without status, system messages cancel it
+            self.running_status = (byte < SYSTEM_MASK).then_some(byte);
+            self.buffer.push(byte);
+        } else {
+            if self.skipping_sysex {
+                return;
+            }
+            if self.buffer.is_empty() {
+                let Some(status) = self.running_status else {
+                    messages.push(Err(MidiMessageError::UnexpectedData(byte)));
+                    return;
+                };
+                self.buffer.push(status);
+            }
+            self.buffer.push(byte);
+        }
+
+        let status = self.buffer[0];
+        if status == SYSEX_MESSAGE_MASK {
+            // Without the status byte
+            if self.buffer.len() - 1 > self.max_sysex {
+                self.buffer.clear();
+                self.skipping_sysex = true;
+                messages.push(Err(MidiMessageError::InvalidLength));
+            }
+        } else if self.buffer.len() >= message_length(status) {
+            messages.push(MidiMessage::from(&self.buffer));
+            self.buffer.clear();
+        }
+    }
+}
+
+/// Encodes MIDI messages for a byte stream, leaving out repeated status bytes
+#[derive(Debug, Default)]
+pub struct StreamEncoder {
+    /// Status of the last channel message the receiver has seen
+    running_status: Option<u8>,
+}
+
+impl StreamEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `message` to `out`, the status byte is omitted if it is the running status
+    pub fn encode(&mut self, message: &MidiMessage, out: &mut Vec<u8>) {
+        let bytes = message.to_bytes();
+        let status = bytes[0];
+        match status {
+            // Realtime messages do not change the running status
+            TIMING_CLOCK_MASK.. => {}
+            SYSTEM_MASK.. => self.running_status = None,
+            _ if self.running_status == Some(status) => {
+                out.extend_from_slice(&bytes[1..]);
+                return;
+            }
+            _ => self.running_status = Some(status),
+        }
+        out.extend_from_slice(&bytes);
+    }
+
+    /// Sends the next status byte again, e.g. after the receiver has been reconnected
+    pub fn reset(&mut self) {
+        self.running_status = None;
+    }
+}
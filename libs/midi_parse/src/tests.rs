@@ -15,7 +15,7 @@ mod tests {
         );
 
         let data_check = result.to_bytes();
-        assert_eq!(data, data_check);
+        assert_eq!(data[..], *data_check);
     }
 
     #[test]
@@ -32,7 +32,7 @@ mod tests {
         );
 
         let data_check = result.to_bytes();
-        assert_eq!(data, data_check);
+        assert_eq!(data[..], *data_check);
     }
 
     #[test]
@@ -48,7 +48,7 @@ mod tests {
         );
 
         let data_check = result.to_bytes();
-        assert_eq!(data, data_check);
+        assert_eq!(data[..], *data_check);
     }
 
     #[test]
@@ -59,6 +59,639 @@ mod tests {
         assert_eq!(result, MidiMessage::SysExMessage(Box::new(midi_data)));
 
         let data_check = result.to_bytes();
-        assert_eq!(data, data_check);
+        assert_eq!(data[..], *data_check);
+    }
+
+    #[test]
+    fn test_system_messages() {
+        let result = MidiMessage::from(&[0b1111_0010, 0, 1]).unwrap();
+        assert_eq!(
+            result,
+            MidiMessage::SongPositionPointer {
+                position: Position(128)
+            }
+        );
+        assert_eq!(
+            MidiMessage::from(&[0b1111_1000]).unwrap(),
+            MidiMessage::TimingClock
+        );
+        assert_eq!(
+            MidiMessage::from(&[0b1111_1111]).unwrap(),
+            MidiMessage::Reset
+        );
+        assert!(matches!(
+            MidiMessage::from(&[0b1111_0101]),
+            Err(MidiMessageError::InvalidCode(0b1111_0101))
+        ));
+    }
+
+    #[test]
+    fn test_short_input() {
+        for data in [
+            &[][..],
+            &[0b1001_0000, 69],
+            &[0b1111_0010, 0],
+            &[0b1111_0000],
+        ] {
+            assert!(matches!(
+                MidiMessage::from(data),
+                Err(MidiMessageError::InvalidLength)
+            ));
+        }
+    }
+
+    fn parse_all(parser: &mut StreamParser, data: &[u8]) -> Vec<MidiMessage> {
+        parser
+            .parse(data)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn note_on(note: u8, velocity: u8) -> MidiMessage {
+        MidiMessage::NoteOn {
+            channel: Channel(0),
+            note: Note(note),
+            velocity: Velocity(velocity),
+        }
+    }
+
+    #[test]
+    fn test_stream_running_status() {
+        let mut parser = StreamParser::new();
+        // Split across chunks, the second and third note use running status
+        assert_eq!(parse_all(&mut parser, &[0b1001_0000, 60]), []);
+        assert_eq!(
+            parse_all(&mut parser, &[127, 62, 100, 64]),
+            [note_on(60, 127), note_on(62, 100)]
+        );
+        assert_eq!(parse_all(&mut parser, &[0]), [note_on(64, 0)]);
+
+        // System common messages cancel running status
+        let results = parser.parse(&[0b1111_0110, 60, 0]);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), &MidiMessage::TuneRequest);
+        assert!(matches!(
+            results[1],
+            Err(MidiMessageError::UnexpectedData(60))
+        ));
+    }
+
+    #[test]
+    fn test_stream_realtime() {
+        let mut parser = StreamParser::new();
+        let data = [
+            0b1001_0000,
+            0b1111_1000,
+            60,
+            0b1111_1110,
+            127,
+            61,
+            0b1111_1000,
+            127,
+        ];
+        assert_eq!(
+            parse_all(&mut parser, &data),
+            [
+                MidiMessage::TimingClock,
+                MidiMessage::ActiveSensing,
+                note_on(60, 127),
+                MidiMessage::TimingClock,
+                note_on(61, 127),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_sys_ex() {
+        let mut parser = StreamParser::new();
+        assert_eq!(parse_all(&mut parser, &[0b1111_0000, 1, 2]), []);
+        assert_eq!(
+            parse_all(&mut parser, &[3, 0b1111_1000, 4, 0b1111_0111]),
+            [
+                MidiMessage::TimingClock,
+                MidiMessage::SysExMessage(Box::new([1, 2, 3, 4]))
+            ]
+        );
+
+        // A SysEx message interrupted by another status byte is dropped
+        let results = parser.parse(&[0b1111_0000, 1, 2, 0b1001_0000, 60, 127]);
+        assert!(matches!(results[0], Err(MidiMessageError::InvalidLength)));
+        assert_eq!(results[1].as_ref().unwrap(), &note_on(60, 127));
+
+        // A SysEx message that is too long is dropped up to its end
+        let mut parser = StreamParser::with_max_sysex(3);
+        assert_eq!(
+            parse_all(&mut parser, &[0b1111_0000, 1, 2, 3, 0b1111_0111]),
+            [MidiMessage::SysExMessage(Box::new([1, 2, 3]))]
+        );
+        let results = parser.parse(&[0b1111_0000, 1, 2, 3, 4, 5, 0b1111_1000, 6, 0b1111_0111]);
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], Err(MidiMessageError::InvalidLength)));
+        assert_eq!(results[1].as_ref().unwrap(), &MidiMessage::TimingClock);
+        assert_eq!(
+            parse_all(&mut parser, &[0b1001_0000, 60, 127]),
+            [note_on(60, 127)]
+        );
+    }
+
+    #[test]
+    fn test_stream_encoder() {
+        let messages = [
+            note_on(60, 127),
+            MidiMessage::TimingClock,
+            note_on(62, 127),
+            MidiMessage::ControlChange {
+                channel: Channel(0),
+                controller_number: Controller(7),
+                value: Value(100),
+            },
+            MidiMessage::SongSelect { song: Song(1) },
+            note_on(60, 0),
+            MidiMessage::SysExMessage(Box::new([1, 2])),
+        ];
+        let mut encoder = StreamEncoder::new();
+        let mut data = Vec::new();
+        for message in &messages {
+            encoder.encode(message, &mut data);
+        }
+        assert_eq!(
+            data,
+            [
+                0b1001_0000,
+                60,
+                127,
+                0b1111_1000,
+                62,
+                127,
+                0b1011_0000,
+                7,
+                100,
+                0b1111_0011,
+                1,
+                0b1001_0000,
+                60,
+                0,
+                0b1111_0000,
+                1,
+                2,
+                0b1111_0111
+            ]
+        );
+
+        // Any chunking of the encoded stream parses back to the same messages
+        for chunk_size in 1..data.len() {
+            let mut parser = StreamParser::new();
+            let parsed: Vec<_> = data
+                .chunks(chunk_size)
+                .flat_map(|chunk| parse_all(&mut parser, chunk))
+                .collect();
+            assert_eq!(parsed, messages);
+        }
+    }
+
+    fn timecode(hours: u8, minutes: u8, seconds: u8, frames: u8) -> Timecode {
+        Timecode {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            rate: FrameRate::Fps25,
+        }
+    }
+
+    #[test]
+    fn test_quarter_frame() {
+        let data = [0b1111_0001, 0b0111_0011];
+        let result = MidiMessage::from(&data).unwrap();
+        let MidiMessage::QuarterFrame { piece } = &result else {
+            panic!("{result:?}");
+        };
+        assert_eq!((piece.index(), piece.value()), (7, 3));
+        assert_eq!(data[..], *result.to_bytes());
+
+        let mut parser = StreamParser::new();
+        assert_eq!(parse_all(&mut parser, &data), [result]);
+    }
+
+    #[test]
+    fn test_full_frame() {
+        let data = [0xF0, 0x7F, 0x7F, 0x01, 0x01, 0b0010_0001, 2, 3, 4, 0xF7];
+        let message = MidiMessage::from(&data).unwrap();
+        let result = Timecode::from_full_frame(&message).unwrap();
+        assert_eq!(result, timecode(1, 2, 3, 4));
+        assert_eq!(result.to_string(), "01:02:03:04");
+        assert_eq!(data[..], *result.to_full_frame().to_bytes());
+
+        // Other SysEx messages and invalid timecodes are not full frames
+        let other = MidiMessage::SysExMessage(Box::new([0x7F, 0x7F, 0x06, 0x01]));
+        assert_eq!(Timecode::from_full_frame(&other), None);
+        let invalid = MidiMessage::SysExMessage(Box::new([0x7F, 0x7F, 1, 1, 0x20, 0, 0, 25]));
+        assert_eq!(Timecode::from_full_frame(&invalid), None);
+
+        // Values out of range are truncated instead of ending the SysEx or changing the rate
+        let out_of_range = timecode(40, 200, 255, 130);
+        let MidiMessage::SysExMessage(data) = out_of_range.to_full_frame() else {
+            panic!("not a SysEx message");
+        };
+        assert!(data.iter().all(|byte| *byte < 0x80), "{data:?}");
+        assert_eq!(data[4] >> 5, 1);
+        let msc = msc::MscMessage::new(
+            msc::ALL_CALL,
+            msc::CommandFormat::Lighting,
+            msc::MscCommand::TimedGo(out_of_range, None),
+        );
+        let MidiMessage::SysExMessage(data) = msc.to_sysex() else {
+            panic!("not a SysEx message");
+        };
+        assert!(data.iter().all(|byte| *byte < 0x80), "{data:?}");
+    }
+
+    #[test]
+    fn test_mtc_decoder() {
+        use std::time::{Duration, Instant};
+
+        let mut decoder = MtcDecoder::new();
+        let start = Instant::now();
+        let quarter = Duration::from_millis(10);
+        let location = timecode(10, 59, 58, 24);
+        assert_eq!(
+            decoder.decode(&location.to_full_frame(), start),
+            Some(MtcEvent::Located(location))
+        );
+
+        // Starting in the middle, the first complete timecode follows the next piece 7
+        let first = timecode(1, 2, 3, 4);
+        let second = timecode(1, 2, 3, 6);
+        let mut now = start;
+        let mut events = Vec::new();
+        for message in first.to_quarter_frames()[4..]
+            .iter()
+            .chain(&first.to_quarter_frames())
+            .chain(&second.to_quarter_frames())
+        {
+            now += quarter;
+            events.extend(decoder.decode(message, now));
+        }
+        assert_eq!(
+            events,
+            [
+                MtcEvent::Running(first, Direction::Forward),
+                MtcEvent::Running(second, Direction::Forward),
+            ]
+        );
+        assert_eq!(decoder.timecode(), Some(second));
+
+        // Running backward the pieces arrive in reverse order, the turn drops a timecode
+        events.clear();
+        let backward = first.to_quarter_frames();
+        for message in backward.iter().rev().chain(backward.iter().rev()) {
+            now += quarter;
+            events.extend(decoder.decode(message, now));
+        }
+        assert_eq!(events, [MtcEvent::Running(first, Direction::Backward)]);
+
+        // A missing piece drops the incomplete timecode
+        let frames = second.to_quarter_frames();
+        for message in frames[..3].iter().chain(&frames[4..]) {
+            now += quarter;
+            assert_eq!(decoder.decode(message, now), None);
+        }
+
+        assert_eq!(decoder.poll(now + quarter), None);
+        assert!(decoder.is_running());
+        assert_eq!(
+            decoder.poll(now + Duration::from_secs(1)),
+            Some(MtcEvent::Dropout)
+        );
+        assert_eq!(decoder.poll(now + Duration::from_secs(2)), None);
+        assert!(!decoder.is_running());
+    }
+
+    #[test]
+    fn test_msc() {
+        use crate::msc::*;
+
+        // GO cue 1.5 in list 2 for device 1, as sent by a lighting desk
+        let data = [
+            0xF0, 0x7F, 0x01, 0x02, 0x01, 0x01, b'1', b'.', b'5', 0x00, b'2', 0xF7,
+        ];
+        let message = MscMessage::from_sysex(&MidiMessage::from(&data).unwrap()).unwrap();
+        let cue = Cue::new("1.5", Some("2"), None).unwrap();
+        assert_eq!(
+            message,
+            MscMessage::new(1, CommandFormat::Lighting, MscCommand::Go(Some(cue)))
+        );
+        assert_eq!(data[..], *message.to_sysex().to_bytes());
+
+        let commands = [
+            MscCommand::Stop(None),
+            MscCommand::TimedGo(
+                timecode(0, 0, 3, 12),
+                Some(Cue::new("4", Some("1"), Some("7")).unwrap()),
+            ),
+            MscCommand::Load(Cue::new("10", None, None).unwrap()),
+            MscCommand::Set {
+                control: 200,
+                value: 0x3FFF,
+                time: None,
+            },
+            MscCommand::Set {
+                control: 1,
+                value: 0,
+                time: Some(timecode(0, 0, 1, 0)),
+            },
+            MscCommand::Fire(12),
+            MscCommand::AllOff,
+            MscCommand::GoOff(None),
+        ];
+        for command in commands {
+            let message = MscMessage::new(ALL_CALL, CommandFormat::AllTypes, command);
+            assert_eq!(
+                MscMessage::from_sysex(&message.to_sysex()).unwrap(),
+                message
+            );
+        }
+
+        assert!(matches!(
+            Cue::new("1a", None, None),
+            Err(MscError::InvalidCueNumber)
+        ));
+        let other = MidiMessage::SysExMessage(Box::new([0x7F, 0x7F, 0x01, 0x01, 0, 0, 0, 0]));
+        assert!(matches!(
+            MscMessage::from_sysex(&other),
+            Err(MscError::NotMsc)
+        ));
+        let fire = MidiMessage::SysExMessage(Box::new([0x7F, 0x01, 0x02, 0x01, 0x07]));
+        assert!(matches!(
+            MscMessage::from_sysex(&fire),
+            Err(MscError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn test_identity() {
+        use crate::universal::*;
+
+        let request = UniversalMessage::IdentityRequest {
+            device: UniversalMessage::ALL_DEVICES,
+        };
+        assert_eq!(
+            *request.to_sysex().unwrap().to_bytes(),
+            [0xF0, 0x7E, 0x7F, 0x06, 0x01, 0xF7]
+        );
+
+        // An identity reply of a controller with an extended manufacturer id
+        let data = [
+            0xF0, 0x7E, 0x00, 0x06, 0x02, 0x00, 0x20, 0x29, 0x13, 0x01, 0x00, 0x00, 0x00, 0x04,
+            0x05, 0x07, 0xF7,
+        ];
+        let message = MidiMessage::from(&data).unwrap();
+        let reply = UniversalMessage::from_sysex(&message).unwrap();
+        assert_eq!(
+            reply,
+            UniversalMessage::IdentityReply {
+                device: 0,
+                identity: Identity {
+                    manufacturer: ManufacturerId::Extended(0x20, 0x29),
+                    family: 0x0093,
+                    model: 0,
+                    version: [0, 4, 5, 7],
+                }
+            }
+        );
+        assert_eq!(data[..], *reply.to_sysex().unwrap().to_bytes());
+
+        let short = MidiMessage::SysExMessage(Box::new([
+            0x7E, 0x10, 0x06, 0x02, 0x41, 0x01, 0x02, 0x03, 0x04, 0x00, 0x00, 0x00, 0x01,
+        ]));
+        let UniversalMessage::IdentityReply { identity, .. } =
+            UniversalMessage::from_sysex(&short).unwrap()
+        else {
+            panic!("Not an identity reply");
+        };
+        assert_eq!(identity.manufacturer, ManufacturerId::Short(0x41));
+        assert_eq!((identity.family, identity.model), (0x0101, 0x0203));
+
+        // Values above 7 bits are split into two data bytes and do not corrupt the stream
+        let reply = UniversalMessage::IdentityReply {
+            device: 1,
+            identity: Identity {
+                manufacturer: ManufacturerId::Short(0x41),
+                family: 0x0080,
+                model: 0x3FFF,
+                version: [1, 2, 3, 0x84],
+            },
+        };
+        let data = reply.to_sysex().unwrap().to_bytes();
+        let body = &data[1..data.len() - 1];
+        assert!(body.iter().all(|byte| byte & 0b1000_0000 == 0));
+        let mut parser = StreamParser::new();
+        let [message] = &parse_all(&mut parser, &data)[..] else {
+            panic!("Expected a single message");
+        };
+        let UniversalMessage::IdentityReply { identity, .. } =
+            UniversalMessage::from_sysex(message).unwrap()
+        else {
+            panic!("Not an identity reply");
+        };
+        assert_eq!((identity.family, identity.model), (0x0080, 0x3FFF));
+        assert_eq!(identity.version, [1, 2, 3, 0x04]);
+    }
+
+    #[test]
+    fn test_machine_control() {
+        use crate::universal::*;
+
+        let data = [0xF0, 0x7F, 0x7F, 0x06, 0x02, 0xF7];
+        let play = UniversalMessage::from_sysex(&MidiMessage::from(&data).unwrap()).unwrap();
+        assert_eq!(
+            play,
+            UniversalMessage::MachineControl {
+                device: 0x7F,
+                command: MmcCommand::Play
+            }
+        );
+        assert_eq!(data[..], *play.to_sysex().unwrap().to_bytes());
+
+        let data = [
+            0xF0, 0x7F, 0x01, 0x06, 0x44, 0x06, 0x01, 0x21, 0x02, 0x03, 0x04, 0x00, 0xF7,
+        ];
+        let locate = UniversalMessage::from_sysex(&MidiMessage::from(&data).unwrap()).unwrap();
+        assert_eq!(
+            locate,
+            UniversalMessage::MachineControl {
+                device: 1,
+                command: MmcCommand::Locate(timecode(1, 2, 3, 4))
+            }
+        );
+        assert_eq!(data[..], *locate.to_sysex().unwrap().to_bytes());
+
+        for command in [
+            MmcCommand::Stop,
+            MmcCommand::RecordStrobe,
+            MmcCommand::Other(0x47, Box::new([0x01, 0x02])),
+        ] {
+            let message = UniversalMessage::MachineControl { device: 2, command };
+            assert_eq!(
+                UniversalMessage::from_sysex(&message.to_sysex().unwrap()).unwrap(),
+                message
+            );
+        }
+        // Data bytes are truncated, data that does not fit is rejected
+        let message = |command| UniversalMessage::MachineControl { device: 2, command };
+        let masked = message(MmcCommand::Other(0x47, Box::new([0x81]))).to_sysex();
+        assert_eq!(
+            *masked.unwrap().to_bytes(),
+            [0xF0, 0x7F, 0x02, 0x06, 0x47, 0x01, 0x01, 0xF7]
+        );
+        for command in [
+            MmcCommand::Other(0x47, vec![0; 128].into()),
+            MmcCommand::Other(0x20, Box::new([0x01])),
+        ] {
+            assert!(matches!(
+                message(command).to_sysex(),
+                Err(UniversalError::InvalidLength)
+            ));
+        }
+
+        // MTC full frames and MSC are read by their own types
+        let full_frame = timecode(0, 0, 0, 0).to_full_frame();
+        assert!(matches!(
+            UniversalMessage::from_sysex(&full_frame),
+            Err(UniversalError::Unsupported(0x01, 0x01))
+        ));
+        let vendor = MidiMessage::SysExMessage(Box::new([0x41, 0x10, 0x42]));
+        assert!(matches!(
+            UniversalMessage::from_sysex(&vendor),
+            Err(UniversalError::NotUniversal)
+        ));
+    }
+
+    fn control_change(controller: u8, value: u8) -> MidiMessage {
+        MidiMessage::ControlChange {
+            channel: Channel(2),
+            controller_number: Controller(controller),
+            value: Value(value),
+        }
+    }
+
+    #[test]
+    fn test_controller_pairs() {
+        let mut aggregator = ControllerAggregator::new();
+        let events: Vec<_> = [
+            control_change(7, 0x40),
+            control_change(39, 0x10),
+            control_change(64, 127),
+            // An LSB without its MSB is not part of a pair
+            control_change(40, 1),
+        ]
+        .iter()
+        .filter_map(|message| aggregator.process(message))
+        .collect();
+        let fine = ControllerEvent::Cc14 {
+            channel: Channel(2),
+            controller: Controller(7),
+            value: 0x2010,
+        };
+        assert_eq!(
+            events,
+            [
+                ControllerEvent::Cc14 {
+                    channel: Channel(2),
+                    controller: Controller(7),
+                    value: 0x2000,
+                },
+                fine.clone(),
+                ControllerEvent::Cc {
+                    channel: Channel(2),
+                    controller: Controller(64),
+                    value: Value(127),
+                },
+                ControllerEvent::Cc {
+                    channel: Channel(2),
+                    controller: Controller(40),
+                    value: Value(1),
+                },
+            ]
+        );
+        assert_eq!(
+            fine.to_messages(),
+            [control_change(7, 0x40), control_change(39, 0x10)]
+        );
+        // Controllers above 31 are truncated instead of overflowing
+        let high = ControllerEvent::Cc14 {
+            channel: Channel(2),
+            controller: Controller(224),
+            value: 0x2010,
+        };
+        assert_eq!(
+            high.to_messages(),
+            [control_change(0, 0x40), control_change(32, 0x10)]
+        );
+
+        // Controllers that are not pairs stay separate
+        let mut aggregator = ControllerAggregator::with_pairs([1]);
+        assert_eq!(
+            aggregator.process(&control_change(7, 0x40)),
+            Some(ControllerEvent::Cc {
+                channel: Channel(2),
+                controller: Controller(7),
+                value: Value(0x40),
+            })
+        );
+        assert_eq!(aggregator.process(&MidiMessage::Stop), None);
+    }
+
+    #[test]
+    fn test_controller_parameters() {
+        let mut aggregator = ControllerAggregator::new();
+        let nrpn = ControllerEvent::Nrpn {
+            channel: Channel(2),
+            param: 0x0105,
+            value: 0x1234,
+        };
+        let messages = nrpn.to_messages();
+        assert_eq!(
+            messages,
+            [
+                control_change(99, 0x02),
+                control_change(98, 0x05),
+                control_change(6, 0x24),
+                control_change(38, 0x34),
+            ]
+        );
+        let events: Vec<_> = messages
+            .iter()
+            .filter_map(|message| aggregator.process(message))
+            .collect();
+        assert_eq!(events.last(), Some(&nrpn));
+        assert_eq!(events.len(), 2);
+
+        // Pitch bend range, then the null parameter makes data entry a pair again
+        let rpn = ControllerEvent::Rpn {
+            channel: Channel(2),
+            param: 0,
+            value: 12 << 7,
+        };
+        let events: Vec<_> = rpn
+            .to_messages()
+            .iter()
+            .chain(&[control_change(101, 127), control_change(100, 127)])
+            .chain(&[control_change(6, 1)])
+            .filter_map(|message| aggregator.process(message))
+            .collect();
+        assert_eq!(
+            events,
+            [
+                rpn.clone(),
+                rpn,
+                ControllerEvent::Cc14 {
+                    channel: Channel(2),
+                    controller: Controller(6),
+                    value: 1 << 7,
+                },
+            ]
+        );
     }
 }
@@ -46,6 +46,22 @@ MidiType!(Program, u8, InvalidProgram);
 MidiType!(Pitch, u16, InvalidPitch);
 MidiType!(Position, u16, InvalidPosition);
 MidiType!(Song, u8, InvalidSong);
+MidiType!(TimecodePiece, u8, InvalidTimecodePiece);
+
+impl TimecodePiece {
+    /// `index` from `0` to `7` selects the part of the timecode, `value` holds its 4 bits
+    pub fn new(index: u8, value: u8) -> TimecodePiece {
+        TimecodePiece((index & 0b0111) << 4 | value & 0b1111)
+    }
+
+    pub fn index(&self) -> u8 {
+        self.0 >> 4
+    }
+
+    pub fn value(&self) -> u8 {
+        self.0 & 0b1111
+    }
+}
 
 fn parse_u7(value: u8) -> Result<u8, ()> {
     if value & 0b1000_0000 == 0 {
@@ -62,4 +78,16 @@ fn parse_u14(pitch2: u8, pitch1: u8) -> Result<u16, ()> {
     } else {
         Err(())
     }
-}
\ No newline at end of file
+}
+
+/// Two bytes of 7 bits, least significant first
+pub(crate) fn u14([lsb, msb]: [u8; 2]) -> u16 {
+    (msb as u16 & 0b0111_1111) << 7 | lsb as u16 & 0b0111_1111
+}
+
+pub(crate) fn u14_bytes(value: u16) -> [u8; 2] {
+    [
+        (value & 0b0111_1111) as u8,
+        (value >> 7 & 0b0111_1111) as u8,
+    ]
+}
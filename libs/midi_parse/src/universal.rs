@@ -0,0 +1,277 @@
+//! Universal SysEx messages to identify devices and to control transports
+//!
+//! [`UniversalMessage::from_sysex`] and [`UniversalMessage::to_sysex`] convert them from and to
+//! [`MidiMessage::SysExMessage`]. MTC full frames are read by [`crate::Timecode`] and MSC
+//! messages by [`crate::msc`].
+
+use crate::consts::{ALL_DEVICES, UNIVERSAL_NON_REALTIME, UNIVERSAL_REALTIME};
+use crate::types::{u14, u14_bytes};
+use crate::{MidiMessage, Timecode};
+
+/// Sub id of the general information messages, followed by the request or reply id
+const GENERAL_INFORMATION: u8 = 0x06;
+const IDENTITY_REQUEST: u8 = 0x01;
+const IDENTITY_REPLY: u8 = 0x02;
+/// Sub id of MMC commands, the realtime counterpart of the general information
+const MACHINE_CONTROL: u8 = 0x06;
+/// Manufacturer ids starting with this byte are followed by two more bytes
+const EXTENDED_MANUFACTURER: u8 = 0x00;
+
+const MMC_STOP: u8 = 0x01;
+const MMC_PLAY: u8 = 0x02;
+const MMC_DEFERRED_PLAY: u8 = 0x03;
+const MMC_FAST_FORWARD: u8 = 0x04;
+const MMC_REWIND: u8 = 0x05;
+const MMC_RECORD_STROBE: u8 = 0x06;
+const MMC_RECORD_EXIT: u8 = 0x07;
+const MMC_RECORD_PAUSE: u8 = 0x08;
+const MMC_PAUSE: u8 = 0x09;
+const MMC_EJECT: u8 = 0x0A;
+const MMC_CHASE: u8 = 0x0B;
+const MMC_RESET: u8 = 0x0D;
+const MMC_LOCATE: u8 = 0x44;
+/// Locate to the time that follows, instead of a stored cue point
+const LOCATE_TARGET: u8 = 0x01;
+/// Commands from this one on are followed by the length of their data
+const MMC_FIRST_WITH_DATA: u8 = 0x40;
+
+#[derive(Debug)]
+pub enum UniversalError {
+    /// The message is not a universal SysEx message
+    NotUniversal,
+    /// A universal SysEx message with these sub ids, which is not supported
+    Unsupported(u8, u8),
+    InvalidTime,
+    InvalidLength,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum ManufacturerId {
+    Short(u8),
+    /// Ids of newer manufacturers, without the leading zero byte
+    Extended(u8, u8),
+}
+
+/// What a device reports about itself, family and model are specific to the manufacturer
+///
+/// Family and model range from `0` to `0x3FFF` and are sent as two bytes of 7 bits each, the
+/// least significant byte first. Values outside of their ranges are truncated to 7 or 14 bits.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Identity {
+    pub manufacturer: ManufacturerId,
+    pub family: u16,
+    pub model: u16,
+    pub version: [u8; 4],
+}
+
+/// A MIDI Machine Control command for recorders and other transports
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum MmcCommand {
+    Stop,
+    Play,
+    DeferredPlay,
+    FastForward,
+    Rewind,
+    RecordStrobe,
+    RecordExit,
+    RecordPause,
+    Pause,
+    Eject,
+    Chase,
+    Reset,
+    Locate(Timecode),
+    /// Any other command with its data, without the length byte
+    ///
+    /// Only commands from `0x40` on carry data, up to 127 bytes of 7 bits each.
+    Other(u8, Box<[u8]>),
+}
+
+impl MmcCommand {
+    fn from_bytes(data: &[u8]) -> Result<MmcCommand, UniversalError> {
+        let [command, data @ ..] = data else {
+            return Err(UniversalError::InvalidLength);
+        };
+        let data = if *command < MMC_FIRST_WITH_DATA {
+            data
+        } else {
+            match data {
+                [length, data @ ..] if data.len() == *length as usize => data,
+                _ => return Err(UniversalError::InvalidLength),
+            }
+        };
+
+        let command = match (*command, data) {
+            (MMC_STOP, []) => MmcCommand::Stop,
+            (MMC_PLAY, []) => MmcCommand::Play,
+            (MMC_DEFERRED_PLAY, []) => MmcCommand::DeferredPlay,
+            (MMC_FAST_FORWARD, []) => MmcCommand::FastForward,
+            (MMC_REWIND, []) => MmcCommand::Rewind,
+            (MMC_RECORD_STROBE, []) => MmcCommand::RecordStrobe,
+            (MMC_RECORD_EXIT, []) => MmcCommand::RecordExit,
+            (MMC_RECORD_PAUSE, []) => MmcCommand::RecordPause,
+            (MMC_PAUSE, []) => MmcCommand::Pause,
+            (MMC_EJECT, []) => MmcCommand::Eject,
+            (MMC_CHASE, []) => MmcCommand::Chase,
+            (MMC_RESET, []) => MmcCommand::Reset,
+            (MMC_LOCATE, [LOCATE_TARGET, time @ ..]) => MmcCommand::Locate(
+                Timecode::from_time_bytes(time).ok_or(UniversalError::InvalidTime)?,
+            ),
+            (command, data) if command >= MMC_FIRST_WITH_DATA || data.is_empty() => {
+                MmcCommand::Other(command, data.into())
+            }
+            // Only one command per message is supported
+            _ => return Err(UniversalError::InvalidLength),
+        };
+        Ok(command)
+    }
+
+    fn write_bytes(&self, data: &mut Vec<u8>) -> Result<(), UniversalError> {
+        let command = match self {
+            MmcCommand::Stop => MMC_STOP,
+            MmcCommand::Play => MMC_PLAY,
+            MmcCommand::DeferredPlay => MMC_DEFERRED_PLAY,
+            MmcCommand::FastForward => MMC_FAST_FORWARD,
+            MmcCommand::Rewind => MMC_REWIND,
+            MmcCommand::RecordStrobe => MMC_RECORD_STROBE,
+            MmcCommand::RecordExit => MMC_RECORD_EXIT,
+            MmcCommand::RecordPause => MMC_RECORD_PAUSE,
+            MmcCommand::Pause => MMC_PAUSE,
+            MmcCommand::Eject => MMC_EJECT,
+            MmcCommand::Chase => MMC_CHASE,
+            MmcCommand::Reset => MMC_RESET,
+            MmcCommand::Locate(time) => {
+                data.extend_from_slice(&[MMC_LOCATE, 6, LOCATE_TARGET]);
+                data.extend_from_slice(&time.to_time_bytes());
+                return Ok(());
+            }
+            MmcCommand::Other(command, command_data) => {
+                let command = command & 0b0111_1111;
+                data.push(command);
+                if command >= MMC_FIRST_WITH_DATA {
+                    let length = u8::try_from(command_data.len())
+                        .ok()
+                        .filter(|length| *length <= 0b0111_1111)
+                        .ok_or(UniversalError::InvalidLength)?;
+                    data.push(length);
+                    data.extend(command_data.iter().map(|byte| byte & 0b0111_1111));
+                } else if !command_data.is_empty() {
+                    return Err(UniversalError::InvalidLength);
+                }
+                return Ok(());
+            }
+        };
+        data.push(command);
+        Ok(())
+    }
+}
+
+/// Device ids range from `0` to `0x7E`, [`UniversalMessage::ALL_DEVICES`] addresses all of them
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum UniversalMessage {
+    /// Asks devices to answer with an identity reply
+    IdentityRequest {
+        device: u8,
+    },
+    IdentityReply {
+        device: u8,
+        identity: Identity,
+    },
+    MachineControl {
+        device: u8,
+        command: MmcCommand,
+    },
+}
+
+impl UniversalMessage {
+    pub const ALL_DEVICES: u8 = ALL_DEVICES;
+
+    pub fn from_sysex(message: &MidiMessage) -> Result<UniversalMessage, UniversalError> {
+        let MidiMessage::SysExMessage(data) = message else {
+            return Err(UniversalError::NotUniversal);
+        };
+        let [id @ (UNIVERSAL_NON_REALTIME | UNIVERSAL_REALTIME), device, sub_id1, data @ ..] =
+            &data[..]
+        else {
+            return Err(UniversalError::NotUniversal);
+        };
+        let device = *device;
+
+        match (*id, *sub_id1, data) {
+            (UNIVERSAL_NON_REALTIME, GENERAL_INFORMATION, [IDENTITY_REQUEST]) => {
+                Ok(UniversalMessage::IdentityRequest { device })
+            }
+            (UNIVERSAL_NON_REALTIME, GENERAL_INFORMATION, [IDENTITY_REPLY, reply @ ..]) => {
+                let (manufacturer, reply) = match reply {
+                    [EXTENDED_MANUFACTURER, id1, id2, reply @ ..] => {
+                        (ManufacturerId::Extended(*id1, *id2), reply)
+                    }
+                    [id, reply @ ..] => (ManufacturerId::Short(*id), reply),
+                    [] => return Err(UniversalError::InvalidLength),
+                };
+                let [family1, family2, model1, model2, version @ ..] = reply else {
+                    return Err(UniversalError::InvalidLength);
+                };
+                let identity = Identity {
+                    manufacturer,
+                    family: u14([*family1, *family2]),
+                    model: u14([*model1, *model2]),
+                    version: version
+                        .try_into()
+                        .map_err(|_| UniversalError::InvalidLength)?,
+                };
+                Ok(UniversalMessage::IdentityReply { device, identity })
+            }
+            (UNIVERSAL_REALTIME, MACHINE_CONTROL, command) => {
+                Ok(UniversalMessage::MachineControl {
+                    device,
+                    command: MmcCommand::from_bytes(command)?,
+                })
+            }
+            (_, sub_id1, data) => Err(UniversalError::Unsupported(
+                sub_id1,
+                data.first().copied().unwrap_or_default(),
+            )),
+        }
+    }
+
+    /// Fails with [`UniversalError::InvalidLength`] if the data of a command does not fit
+    pub fn to_sysex(&self) -> Result<MidiMessage, UniversalError> {
+        let mut data = Vec::new();
+        match self {
+            UniversalMessage::IdentityRequest { device } => data.extend_from_slice(&[
+                UNIVERSAL_NON_REALTIME,
+                device & 0b0111_1111,
+                GENERAL_INFORMATION,
+                IDENTITY_REQUEST,
+            ]),
+            UniversalMessage::IdentityReply { device, identity } => {
+                data.extend_from_slice(&[
+                    UNIVERSAL_NON_REALTIME,
+                    device & 0b0111_1111,
+                    GENERAL_INFORMATION,
+                    IDENTITY_REPLY,
+                ]);
+                match identity.manufacturer {
+                    ManufacturerId::Short(id) => data.push(id & 0b0111_1111),
+                    ManufacturerId::Extended(id1, id2) => data.extend_from_slice(&[
+                        EXTENDED_MANUFACTURER,
+                        id1 & 0b0111_1111,
+                        id2 & 0b0111_1111,
+                    ]),
+                }
+                data.extend_from_slice(&u14_bytes(identity.family));
+                data.extend_from_slice(&u14_bytes(identity.model));
+                data.extend(identity.version.iter().map(|byte| byte & 0b0111_1111));
+            }
+            UniversalMessage::MachineControl { device, command } => {
+                data.extend_from_slice(&[
+                    UNIVERSAL_REALTIME,
+                    device & 0b0111_1111,
+                    MACHINE_CONTROL,
+                ]);
+                command.write_bytes(&mut data)?;
+            }
+        }
+        Ok(MidiMessage::SysExMessage(data.into()))
+    }
+}
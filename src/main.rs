@@ -2,7 +2,7 @@ mod frontend;
 mod midi;
 mod resolume;
 
-use grandma2::{interface::MaEvent, ButtonExecutor, FaderExecutor, GrandMa2};
+use grandma2::{ButtonExecutor, FaderExecutor, GrandMa2};
 
 use midi_parse::{Channel, MidiMessage, Note, Velocity};
 use tokio::sync::mpsc;
@@ -26,6 +26,8 @@ async fn main() {
 
     let mut grandma = GrandMa2::new("ws://10.1.1.10", "remote", "remote");
     let mut grandma_conn = grandma.connect().await.unwrap();
+    grandma.subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 8)).unwrap();
+    grandma.subscribe_button(ButtonExecutor::new(1, 101), ButtonExecutor::new(1, 191)).unwrap();
 
     let main_loop = async move {
         loop {
@@ -53,17 +55,7 @@ async fn main() {
                     }
                 }
                 Ok(msg) = grandma.recv() => {
-                    match msg {
-                        MaEvent::LoginSuccessful(state) => {
-                            if state {
-                                grandma.subscribe_fader(FaderExecutor::new(1, 1), FaderExecutor::new(1, 8)).unwrap();
-                                grandma.subscribe_button(ButtonExecutor::new(1, 101), ButtonExecutor::new(1, 191)).unwrap();
-                            }
-                        }
-                        _ => {
-                            println!("Received {msg:?}")
-                        }
-                    }
+                    println!("Received {msg:?}")
                 }
             }
         }